@@ -0,0 +1,460 @@
+//! Call an rspc server from Rust.
+//!
+//! Queries and mutations are sent over HTTP, either one request at a time or together using [`Client::batch`].
+//! Subscriptions are multiplexed over a single websocket connection which is opened when the first subscription is started.
+//!
+//! Typed functions for every procedure of a router can be generated using `rspc::export::Rust`.
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use rspc_client::Client;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), rspc_client::Error> {
+//! let client = Client::new("http://localhost:4000/rspc");
+//!
+//! let version: String = client.query("version", &()).await?;
+//!
+//! let mut batch = client.batch();
+//! let version = batch.query::<_, String>("version", &())?;
+//! let user = batch.mutation::<_, String>("createUser", "Oscar")?;
+//! let mut results = batch.send().await?;
+//! println!("{} {}", results.take(version)?, results.take(user)?);
+//!
+//! let mut count = client.subscription::<_, u32>("count", &()).await?;
+//! while let Some(n) = count.next().await {
+//!     println!("{}", n?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+#![forbid(unsafe_code)]
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex as StdMutex,
+    },
+    task::{ready, Context, Poll},
+};
+
+use futures::{SinkExt, Stream, StreamExt};
+use rspc::internal::exec::{Request, Response, ResponseError, ResponseInner};
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::sync::{mpsc, Mutex};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+/// An error calling a procedure.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The procedure returned an error.
+    #[error("procedure returned error {}: {}", .0.code, .0.message)]
+    Procedure(ResponseError),
+    #[error("error sending HTTP request: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("websocket error: {0}")]
+    Websocket(Box<tungstenite::Error>),
+    #[error("error serializing the input or deserializing the result: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The server responded with something which isn't an rspc response. Eg. the context function failed.
+    #[error("the server responded with status {0} which isn't a valid rspc response")]
+    InvalidResponse(u16),
+    /// The websocket connection was closed while a subscription was running.
+    #[error("the websocket connection was closed")]
+    Closed,
+}
+
+impl From<tungstenite::Error> for Error {
+    fn from(err: tungstenite::Error) -> Self {
+        Self::Websocket(Box::new(err))
+    }
+}
+
+/// A client for an rspc server mounted at a URL. Cloning the client is cheap and clones share the same websocket connection.
+#[derive(Clone)]
+pub struct Client {
+    url: String,
+    http: reqwest::Client,
+    ws: Arc<Mutex<Option<Connection>>>,
+    next_id: Arc<AtomicU32>,
+}
+
+impl Client {
+    /// Create a client for the rspc server at `url`, eg. `http://localhost:4000/rspc`.
+    pub fn new(url: impl Into<String>) -> Self {
+        let mut url = url.into();
+        while url.ends_with('/') {
+            url.pop();
+        }
+
+        Self {
+            url,
+            http: reqwest::Client::new(),
+            ws: Default::default(),
+            next_id: Default::default(),
+        }
+    }
+
+    /// Use a custom HTTP client. Eg. to set default headers for authentication.
+    pub fn with_http_client(self, http: reqwest::Client) -> Self {
+        Self { http, ..self }
+    }
+
+    /// The URL of the server.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Call a query over HTTP. The key can include a version, eg. `users.get@v2`.
+    pub async fn query<TArg, TResult>(&self, key: &str, input: &TArg) -> Result<TResult, Error>
+    where
+        TArg: Serialize + ?Sized,
+        TResult: DeserializeOwned,
+    {
+        let req = self
+            .http
+            .get(format!("{}/{key}", self.url))
+            .query(&[("input", serde_json::to_string(input)?)]);
+        self.send(req).await
+    }
+
+    /// Call a mutation over HTTP.
+    pub async fn mutation<TArg, TResult>(&self, key: &str, input: &TArg) -> Result<TResult, Error>
+    where
+        TArg: Serialize + ?Sized,
+        TResult: DeserializeOwned,
+    {
+        let req = self
+            .http
+            .post(format!("{}/{key}", self.url))
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_vec(input)?);
+        self.send(req).await
+    }
+
+    /// Start a batch of queries and mutations which are sent in a single HTTP request.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch {
+            client: self,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Start a subscription over the websocket connection, opening it if required.
+    ///
+    /// The stream ends when the subscription completes and the subscription is stopped on the server when the stream is dropped.
+    pub async fn subscription<TArg, TResult>(
+        &self,
+        key: &str,
+        input: &TArg,
+    ) -> Result<Subscription<TResult>, Error>
+    where
+        TArg: Serialize + ?Sized,
+        TResult: DeserializeOwned,
+    {
+        let input = serde_json::to_value(input)?;
+        let conn = self.connect().await?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        conn.subscriptions
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .insert(id, tx);
+
+        // Dropping the subscription cleans up if the request can't be sent.
+        let subscription = Subscription {
+            id,
+            rx,
+            conn,
+            done: false,
+            phantom: PhantomData,
+        };
+        subscription
+            .conn
+            .tx
+            .send(Request::Subscription {
+                id,
+                path: Cow::Owned(key.to_string()),
+                input: Some(input),
+            })
+            .map_err(|_| Error::Closed)?;
+
+        Ok(subscription)
+    }
+
+    async fn send<TResult: DeserializeOwned>(
+        &self,
+        req: reqwest::RequestBuilder,
+    ) -> Result<TResult, Error> {
+        let resp = req.send().await?;
+        let status = resp.status().as_u16();
+        let body = resp.bytes().await?;
+
+        match serde_json::from_slice(&body) {
+            Ok(inner) => result(inner, status),
+            Err(_) => Err(Error::InvalidResponse(status)),
+        }
+    }
+
+    /// Get the websocket connection or open a new one if it's not connected.
+    async fn connect(&self) -> Result<Connection, Error> {
+        let mut ws = self.ws.lock().await;
+        if let Some(conn) = ws.as_ref().filter(|conn| !conn.tx.is_closed()) {
+            return Ok(conn.clone());
+        }
+
+        let url = match self.url.split_once("://") {
+            Some(("https", rest)) => format!("wss://{rest}/ws"),
+            Some(("http", rest)) => format!("ws://{rest}/ws"),
+            _ => format!("{}/ws", self.url),
+        };
+        let (socket, _) = tokio_tungstenite::connect_async(url).await?;
+        let (mut sink, mut stream) = socket.split();
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Request>();
+        let subscriptions = Subscriptions::default();
+
+        tokio::spawn({
+            let subscriptions = subscriptions.clone();
+            async move {
+                loop {
+                    tokio::select! {
+                        req = rx.recv() => {
+                            // Every client and subscription using the connection has been dropped.
+                            let Some(req) = req else {
+                                let _ = sink.close().await;
+                                break;
+                            };
+
+                            let Ok(msg) = serde_json::to_string(&req) else {
+                                continue;
+                            };
+                            if sink.send(Message::Text(msg)).await.is_err() {
+                                break;
+                            }
+                        }
+                        msg = stream.next() => match msg {
+                            Some(Ok(Message::Text(msg))) => dispatch(&subscriptions, msg.as_bytes()),
+                            Some(Ok(Message::Binary(msg))) => dispatch(&subscriptions, &msg),
+                            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                            Some(Ok(_)) => {}
+                        }
+                    }
+                }
+
+                for (_, tx) in subscriptions
+                    .lock()
+                    .unwrap_or_else(|err| err.into_inner())
+                    .drain()
+                {
+                    let _ = tx.send(Err(Error::Closed));
+                }
+            }
+        });
+
+        let conn = Connection { tx, subscriptions };
+        *ws = Some(conn.clone());
+        Ok(conn)
+    }
+}
+
+/// Convert a response into the result of a procedure. `status` is reported if the response isn't a result.
+fn result<TResult: DeserializeOwned>(inner: ResponseInner, status: u16) -> Result<TResult, Error> {
+    match inner {
+        ResponseInner::Value(value) => Ok(serde_json::from_value(value)?),
+        ResponseInner::Error(err) => Err(Error::Procedure(err)),
+        ResponseInner::Complete => Err(Error::InvalidResponse(status)),
+    }
+}
+
+/// A set of queries and mutations to send in a single HTTP request. Created with [`Client::batch`].
+pub struct Batch<'a> {
+    client: &'a Client,
+    requests: Vec<Request>,
+}
+
+impl Batch<'_> {
+    /// Add a query to the batch. The returned key is used to take the result from [`BatchResults`].
+    pub fn query<TArg, TResult>(
+        &mut self,
+        key: &str,
+        input: &TArg,
+    ) -> Result<BatchKey<TResult>, Error>
+    where
+        TArg: Serialize + ?Sized,
+        TResult: DeserializeOwned,
+    {
+        let id = self.requests.len() as u32;
+        self.requests.push(Request::Query {
+            id,
+            path: Cow::Owned(key.to_string()),
+            input: Some(serde_json::to_value(input)?),
+        });
+        Ok(BatchKey::new(id))
+    }
+
+    /// Add a mutation to the batch. The returned key is used to take the result from [`BatchResults`].
+    pub fn mutation<TArg, TResult>(
+        &mut self,
+        key: &str,
+        input: &TArg,
+    ) -> Result<BatchKey<TResult>, Error>
+    where
+        TArg: Serialize + ?Sized,
+        TResult: DeserializeOwned,
+    {
+        let id = self.requests.len() as u32;
+        self.requests.push(Request::Mutation {
+            id,
+            path: Cow::Owned(key.to_string()),
+            input: Some(serde_json::to_value(input)?),
+        });
+        Ok(BatchKey::new(id))
+    }
+
+    /// Send the batch to the server's `_batch` endpoint.
+    pub async fn send(self) -> Result<BatchResults, Error> {
+        let resp = self
+            .client
+            .http
+            .post(format!("{}/_batch", self.client.url))
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_vec(&self.requests)?)
+            .send()
+            .await?;
+        let status = resp.status().as_u16();
+        let body = resp.bytes().await?;
+
+        let responses = serde_json::from_slice::<Vec<Response>>(&body)
+            .map_err(|_| Error::InvalidResponse(status))?;
+
+        Ok(BatchResults {
+            responses: responses
+                .into_iter()
+                .map(|resp| (resp.id, resp.inner))
+                .collect(),
+            status,
+        })
+    }
+}
+
+/// Identifies the result of a procedure in a [`Batch`].
+#[derive(Debug)]
+pub struct BatchKey<T> {
+    id: u32,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> BatchKey<T> {
+    fn new(id: u32) -> Self {
+        Self {
+            id,
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// The results of a [`Batch`].
+#[derive(Debug)]
+pub struct BatchResults {
+    responses: HashMap<u32, ResponseInner>,
+    status: u16,
+}
+
+impl BatchResults {
+    /// Take the result of a procedure from the batch.
+    pub fn take<T: DeserializeOwned>(&mut self, key: BatchKey<T>) -> Result<T, Error> {
+        match self.responses.remove(&key.id) {
+            Some(inner) => result(inner, self.status),
+            None => Err(Error::InvalidResponse(self.status)),
+        }
+    }
+}
+
+type Subscriptions =
+    Arc<StdMutex<HashMap<u32, mpsc::UnboundedSender<Result<ResponseInner, Error>>>>>;
+
+/// A websocket connection shared by every subscription of a client.
+#[derive(Clone)]
+struct Connection {
+    tx: mpsc::UnboundedSender<Request>,
+    subscriptions: Subscriptions,
+}
+
+/// Route the responses in a message from the server to their subscription.
+fn dispatch(subscriptions: &Subscriptions, msg: &[u8]) {
+    // The server sends an array of responses but accept a single one to be safe.
+    let responses = match serde_json::from_slice::<Vec<Response>>(msg) {
+        Ok(responses) => responses,
+        Err(_) => match serde_json::from_slice::<Response>(msg) {
+            Ok(response) => vec![response],
+            Err(_) => return,
+        },
+    };
+
+    let mut subscriptions = subscriptions.lock().unwrap_or_else(|err| err.into_inner());
+    for Response { id, inner } in responses {
+        let complete = matches!(inner, ResponseInner::Complete);
+        if let Some(tx) = subscriptions.get(&id) {
+            let _ = tx.send(Ok(inner));
+        }
+        if complete {
+            subscriptions.remove(&id);
+        }
+    }
+}
+
+/// A running subscription. Created with [`Client::subscription`].
+pub struct Subscription<T> {
+    id: u32,
+    rx: mpsc::UnboundedReceiver<Result<ResponseInner, Error>>,
+    conn: Connection,
+    done: bool,
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Stream for Subscription<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        Poll::Ready(match ready!(this.rx.poll_recv(cx)) {
+            Some(Ok(ResponseInner::Value(value))) => {
+                Some(serde_json::from_value(value).map_err(Into::into))
+            }
+            Some(Ok(ResponseInner::Error(err))) => Some(Err(Error::Procedure(err))),
+            Some(Err(err)) => {
+                this.done = true;
+                Some(Err(err))
+            }
+            Some(Ok(ResponseInner::Complete)) | None => {
+                this.done = true;
+                None
+            }
+        })
+    }
+}
+
+impl<T> Drop for Subscription<T> {
+    fn drop(&mut self) {
+        self.conn
+            .subscriptions
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .remove(&self.id);
+
+        if !self.done {
+            let _ = self.conn.tx.send(Request::SubscriptionStop { id: self.id });
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::net::{SocketAddr, TcpListener};
+
+use async_stream::stream;
+use futures::{stream, StreamExt};
+use rspc::{ErrorCode, Rspc};
+use rspc_client::{Client, Error};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Serialize, Deserialize, Type, Debug, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+/// Serve a router on a random port and return a client for it.
+fn client() -> Client {
+    let router = R
+        .router()
+        .procedure(
+            "me",
+            R.query(|_, _: ()| User {
+                id: 1,
+                name: "Oscar".into(),
+            }),
+        )
+        .procedure("rename", R.mutation(|_, name: String| User { id: 1, name }))
+        .procedure(
+            "admin",
+            R.query(|_, _: ()| -> Result<(), rspc::Error> {
+                Err(rspc::Error::new(
+                    ErrorCode::Forbidden,
+                    "not an admin".into(),
+                ))
+            }),
+        )
+        .procedure(
+            "count",
+            R.subscription(|_, to: u32| {
+                stream! {
+                    for i in 0..to {
+                        yield i;
+                    }
+                }
+            }),
+        )
+        .procedure(
+            "watchAdmin",
+            R.subscription(|_, _: ()| {
+                Err::<stream::Empty<u32>, _>(rspc::Error::new(
+                    ErrorCode::Forbidden,
+                    "not an admin".into(),
+                ))
+            }),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = axum::Router::new().nest("/rspc", router.endpoint(|| ()).axum());
+    tokio::spawn(async move {
+        axum::Server::from_tcp(listener)
+            .unwrap()
+            .serve(app.into_make_service())
+            .await
+            .unwrap();
+    });
+
+    Client::new(format!("http://{addr}/rspc"))
+}
+
+fn assert_procedure_error<T: std::fmt::Debug>(result: Result<T, Error>, code: ErrorCode) {
+    match result {
+        Err(Error::Procedure(err)) => assert_eq!(err.code, code.to_status_code()),
+        result => panic!("expected a procedure error with code {code:?}, got {result:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_query() {
+    let client = client();
+
+    let me: User = client.query("me", &()).await.unwrap();
+    assert_eq!(
+        me,
+        User {
+            id: 1,
+            name: "Oscar".into()
+        }
+    );
+
+    assert_procedure_error(
+        client.query::<_, ()>("admin", &()).await,
+        ErrorCode::Forbidden,
+    );
+    assert_procedure_error(
+        client.query::<_, ()>("missing", &()).await,
+        ErrorCode::NotFound,
+    );
+    assert_procedure_error(
+        client.query::<_, User>("me", "not a unit").await,
+        ErrorCode::BadRequest,
+    );
+
+    // The result doesn't match the type the caller asked for
+    assert!(matches!(
+        client.query::<_, String>("me", &()).await,
+        Err(Error::Json(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_mutation() {
+    let client = client();
+
+    let user: User = client.mutation("rename", "Monty").await.unwrap();
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "Monty".into()
+        }
+    );
+
+    assert_procedure_error(
+        client.mutation::<_, User>("rename", &42).await,
+        ErrorCode::BadRequest,
+    );
+    // Queries can't be called as mutations
+    assert_procedure_error(
+        client.mutation::<_, User>("me", &()).await,
+        ErrorCode::NotFound,
+    );
+}
+
+#[tokio::test]
+async fn test_batch() {
+    let client = client();
+
+    let mut batch = client.batch();
+    let me = batch.query::<_, User>("me", &()).unwrap();
+    let renamed = batch.mutation::<_, User>("rename", "Monty").unwrap();
+    let admin = batch.query::<_, ()>("admin", &()).unwrap();
+    let missing = batch.query::<_, ()>("missing", &()).unwrap();
+    let mut results = batch.send().await.unwrap();
+
+    assert_eq!(results.take(me).unwrap().name, "Oscar");
+    assert_eq!(results.take(renamed).unwrap().name, "Monty");
+    assert_procedure_error(results.take(admin), ErrorCode::Forbidden);
+    assert_procedure_error(results.take(missing), ErrorCode::NotFound);
+}
+
+#[tokio::test]
+async fn test_subscription() {
+    let client = client();
+
+    let values = client
+        .subscription::<_, u32>("count", &3)
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(values, vec![0, 1, 2]);
+
+    // Both subscriptions share the websocket connection opened by the first one
+    let mut a = client.subscription::<_, u32>("count", &2).await.unwrap();
+    let mut b = client.subscription::<_, u32>("count", &1).await.unwrap();
+    assert_eq!(a.next().await.unwrap().unwrap(), 0);
+    assert_eq!(b.next().await.unwrap().unwrap(), 0);
+    assert!(b.next().await.is_none());
+    assert_eq!(a.next().await.unwrap().unwrap(), 1);
+    assert!(a.next().await.is_none());
+
+    let mut admin = client
+        .subscription::<_, u32>("watchAdmin", &())
+        .await
+        .unwrap();
+    assert_procedure_error(admin.next().await.unwrap(), ErrorCode::Forbidden);
+
+    let mut missing = client.subscription::<_, u32>("missing", &()).await.unwrap();
+    assert_procedure_error(missing.next().await.unwrap(), ErrorCode::NotFound);
+}
@@ -0,0 +1,64 @@
+//! Compare two manifests exported with `BuiltRouter::export_manifest` and exit with a non-zero status code if there are breaking changes.
+//!
+//! ```bash
+//! rspc-diff ./old.manifest.json ./new.manifest.json
+//! ```
+//!
+//! Typescript bindings don't contain enough information to be compared so export a manifest next to them.
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use rspc::Manifest;
+
+const USAGE: &str = "Usage: rspc-diff <old manifest> <new manifest>";
+
+fn read(path: &Path) -> Result<Manifest, String> {
+    let file =
+        fs::read(path).map_err(|err| format!("failed to read '{}': {err}", path.display()))?;
+    let manifest: Manifest = serde_json::from_slice(&file)
+        .map_err(|err| format!("'{}' is not a valid rspc manifest: {err}", path.display()))?;
+
+    if manifest.version != Manifest::VERSION {
+        return Err(format!(
+            "'{}' uses manifest version {} but only version {} is supported",
+            path.display(),
+            manifest.version,
+            Manifest::VERSION
+        ));
+    }
+
+    Ok(manifest)
+}
+
+fn try_main() -> Result<bool, String> {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let [old, new] = &args[..] else {
+        return Err(USAGE.into());
+    };
+
+    let diff = read(Path::new(old))?.diff(&read(Path::new(new))?);
+    if diff.is_empty() {
+        println!("No changes");
+        return Ok(false);
+    }
+
+    for change in &diff.changes {
+        println!("{change}");
+    }
+
+    let breaking = diff.breaking().count();
+    println!("\n{} changes, {breaking} breaking", diff.changes.len());
+
+    Ok(breaking != 0)
+}
+
+fn main() -> ExitCode {
+    match try_main() {
+        Ok(false) => ExitCode::SUCCESS,
+        Ok(true) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -21,6 +21,11 @@ type KeyAndInput = [string] | [string, any];
 type OperationOpts = {
   signal?: AbortSignal;
   context?: OperationContext;
+  /**
+   * The version of the procedure to call, eg. `"latest"`, `"v2"` or `"v1..v3"`.
+   * If the key is already a specific version (eg. `users.get@v2`) it is replaced.
+   */
+  version?: string;
   // skipBatch?: boolean; // TODO: Make this work + add this to React
 };
 
@@ -65,7 +70,7 @@ export class AlphaClient<P extends ProceduresDef> {
         {
           method: "query",
           input: keyAndInput2[1],
-          path: keyAndInput2[0],
+          path: withVersion(keyAndInput2[0], opts?.version),
           context: opts?.context || {},
         },
         this.links
@@ -97,7 +102,7 @@ export class AlphaClient<P extends ProceduresDef> {
         {
           method: "mutation",
           input: keyAndInput2[1],
-          path: keyAndInput2[0],
+          path: withVersion(keyAndInput2[0], opts?.version),
           context: opts?.context || {},
         },
         this.links
@@ -119,7 +124,10 @@ export class AlphaClient<P extends ProceduresDef> {
     TData = inferProcedureResult<P, "subscriptions", K>
   >(
     keyAndInput: [K, ..._inferProcedureHandlerInput<P, "subscriptions", K>],
-    opts: SubscriptionOptions<TData> & { context?: OperationContext }
+    opts: SubscriptionOptions<TData> & {
+      context?: OperationContext;
+      version?: string;
+    }
   ): () => void {
     try {
       const keyAndInput2 = this.mapQueryKey
@@ -130,7 +138,7 @@ export class AlphaClient<P extends ProceduresDef> {
         {
           method: "subscription",
           input: keyAndInput2[1],
-          path: keyAndInput2[0],
+          path: withVersion(keyAndInput2[0], opts?.version),
           context: opts?.context || {},
         },
         this.links
@@ -158,6 +166,12 @@ export class AlphaClient<P extends ProceduresDef> {
   }
 }
 
+// The server resolves the version from the suffix of the path
+function withVersion(key: string, version?: string) {
+  if (version === undefined) return key;
+  return `${key.replace(/@v\d+$/, "")}@${version}`;
+}
+
 function exec(op: Operation, links: Link[]) {
   if (!links[0]) throw new Error("No links provided");
 
@@ -239,17 +239,20 @@ function requestParams(
 ): [string, RequestInit] {
   const headers = generateHeaders(opts, { op: op });
 
-  let url = encodeURIComponent(op.path);
+  // The version is sent in the query string so it isn't percent-encoded as part of the path
+  const [path, version] = op.path.split("@", 2);
+  const params = new URLSearchParams();
+  if (version !== undefined) params.set("version", version);
+
+  let url = encodeURIComponent(path!);
   let body = undefined;
   if (op.method === "query" && op.input !== undefined) {
-    const params = new URLSearchParams({
-      input: JSON.stringify(op.input),
-    });
-    url += "?" + params.toString();
+    params.set("input", JSON.stringify(op.input));
   } else if (op.method === "mutation") {
     headers.set("Content-Type", "application/json");
     body = JSON.stringify(op.input || {});
   }
+  if (params.toString() !== "") url += "?" + params.toString();
 
   return [
     url,
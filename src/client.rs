@@ -0,0 +1,183 @@
+use std::{
+    borrow::Cow,
+    future::{ready, Future},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+use futures::{
+    stream::{once, Stream},
+    StreamExt,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+
+use crate::{
+    internal::{
+        middleware::{ProcedureKind, RequestContext},
+        FutureValueOrStream,
+    },
+    BuiltRouter, Error, ExecError,
+};
+
+/// A typed client for calling the procedures of a router from Rust.
+///
+/// Procedures are executed in-process through the same middleware as requests from a transport, using the context given to each call.
+/// Inputs are serialized and results deserialized using the same `serde` implementations as the other integrations.
+///
+/// ```rust
+/// use futures::StreamExt;
+/// use rspc::{Client, Rspc};
+///
+/// const R: Rspc<()> = Rspc::new();
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), rspc::Error> {
+/// let router = R
+///     .router()
+///     .procedure("greet", R.query(|_, name: String| format!("Hello {name}")))
+///     .procedure(
+///         "count",
+///         R.subscription(|_, _: ()| futures::stream::iter(0..3)),
+///     )
+///     .build()
+///     .unwrap()
+///     .arced();
+///
+/// let client = Client::new(router);
+///
+/// let greeting: String = client.query((), "greet", "Oscar").await?;
+/// assert_eq!(greeting, "Hello Oscar");
+///
+/// let count = client
+///     .subscription::<_, i32>((), "count", ())
+///     .map(Result::unwrap)
+///     .collect::<Vec<_>>()
+///     .await;
+/// assert_eq!(count, vec![0, 1, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Client<TCtx = ()> {
+    router: Arc<BuiltRouter<TCtx>>,
+    next_id: Arc<AtomicU32>,
+}
+
+impl<TCtx> Clone for Client<TCtx> {
+    fn clone(&self) -> Self {
+        Self {
+            router: self.router.clone(),
+            next_id: self.next_id.clone(),
+        }
+    }
+}
+
+impl<TCtx> Client<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    pub fn new(router: Arc<BuiltRouter<TCtx>>) -> Self {
+        Self {
+            router,
+            next_id: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// The router the client calls.
+    pub fn router(&self) -> &Arc<BuiltRouter<TCtx>> {
+        &self.router
+    }
+
+    /// Call a query and deserialize its result.
+    ///
+    /// The key can include a version, eg. `users.get@v2`, otherwise it's resolved the same as a request without a version.
+    pub fn query<TArg, TResult>(
+        &self,
+        ctx: TCtx,
+        key: &str,
+        input: TArg,
+    ) -> impl Future<Output = Result<TResult, Error>> + Send
+    where
+        TArg: Serialize,
+        TResult: DeserializeOwned,
+    {
+        first(self.call(ProcedureKind::Query, ctx, key, input))
+    }
+
+    /// Call a mutation and deserialize its result.
+    pub fn mutation<TArg, TResult>(
+        &self,
+        ctx: TCtx,
+        key: &str,
+        input: TArg,
+    ) -> impl Future<Output = Result<TResult, Error>> + Send
+    where
+        TArg: Serialize,
+        TResult: DeserializeOwned,
+    {
+        first(self.call(ProcedureKind::Mutation, ctx, key, input))
+    }
+
+    /// Start a subscription and deserialize each value it yields. The subscription is stopped when the stream is dropped.
+    pub fn subscription<TArg, TResult>(
+        &self,
+        ctx: TCtx,
+        key: &str,
+        input: TArg,
+    ) -> impl Stream<Item = Result<TResult, Error>> + Send
+    where
+        TArg: Serialize,
+        TResult: DeserializeOwned,
+    {
+        self.call(ProcedureKind::Subscription, ctx, key, input)
+            .map(|result| deserialize(result?))
+    }
+
+    fn call<TArg: Serialize>(
+        &self,
+        kind: ProcedureKind,
+        ctx: TCtx,
+        key: &str,
+        input: TArg,
+    ) -> FutureValueOrStream {
+        let input = match serde_json::to_value(input) {
+            Ok(input) => input,
+            Err(err) => return Box::pin(once(ready(Err(ExecError::SerializingArgErr(err))))),
+        };
+
+        let (key, procedure) = match self.router.store(kind).resolve(key) {
+            Ok(procedure) => procedure,
+            Err(err) => return Box::pin(once(ready(Err(err)))),
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut req = RequestContext::new(id, kind, Cow::Owned(key.to_string()));
+        req.meta = Some(procedure.meta.clone());
+
+        procedure.call(ctx, Some(input), req)
+    }
+}
+
+impl<TCtx> From<Arc<BuiltRouter<TCtx>>> for Client<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    fn from(router: Arc<BuiltRouter<TCtx>>) -> Self {
+        Self::new(router)
+    }
+}
+
+async fn first<TResult: DeserializeOwned>(
+    mut stream: FutureValueOrStream,
+) -> Result<TResult, Error> {
+    match stream.next().await {
+        Some(result) => deserialize(result?),
+        None => Err(ExecError::ErrStreamEmpty.into()),
+    }
+}
+
+fn deserialize<TResult: DeserializeOwned>(value: Value) -> Result<TResult, Error> {
+    serde_json::from_value(value).map_err(|err| ExecError::DeserializingResultErr(err).into())
+}
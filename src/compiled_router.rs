@@ -1,26 +1,25 @@
-use std::{
-    borrow::Cow,
-    collections::BTreeMap,
-    fs::{self, File},
-    io::Write,
-    path::PathBuf,
-    sync::Arc,
-};
+#[cfg(feature = "unstable")]
+use std::collections::BTreeMap;
+use std::{borrow::Cow, path::PathBuf, sync::Arc};
 
-use specta::{
-    ts::{self, datatype, ExportConfiguration, TsExportError},
-    DataType, TypeDefs,
-};
+use specta::{DataType, TypeDefs};
 
+#[cfg(feature = "typescript")]
+use crate::export::{run_formatter, Typescript};
 use crate::{
-    internal::{ProcedureStore, ProcedureTodo},
-    ExportError,
+    export::BigIntExport,
+    internal::{middleware::ProcedureKind, ProcedureStore, ProcedureTodo},
+    ExportError, ProcedureMeta,
 };
 
 /// ExportConfig is used to configure how rspc will export your types.
 pub struct ExportConfig {
     export_path: PathBuf,
     header: Cow<'static, str>,
+    bigint: BigIntExport,
+    formatter: Option<Cow<'static, str>>,
+    split_types: Option<PathBuf>,
+    path_constants: bool,
 }
 
 impl ExportConfig {
@@ -28,6 +27,10 @@ impl ExportConfig {
         ExportConfig {
             export_path: export_path.into(),
             header: Cow::Borrowed(""),
+            bigint: BigIntExport::Fail,
+            formatter: None,
+            split_types: None,
+            path_constants: false,
         }
     }
 
@@ -37,6 +40,70 @@ impl ExportConfig {
             ..self
         }
     }
+
+    /// Configure how `i64`, `u64`, `i128` and `u128` are exported. By default exporting them is an error.
+    pub fn bigint(self, bigint: BigIntExport) -> Self {
+        Self { bigint, ..self }
+    }
+
+    /// Run a command on the exported files once they are written. The paths of the files are appended as arguments.
+    ///
+    /// Eg. `ExportConfig::new("./bindings.ts").formatter("prettier --write")`
+    pub fn formatter(self, command: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            formatter: Some(command.into()),
+            ..self
+        }
+    }
+
+    /// Export every type into its own module in `dir` instead of a single file. `dir` is relative to the directory of the export path.
+    ///
+    /// The exported file re-exports all of the types so existing imports continue to work.
+    pub fn split_types(self, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            split_types: Some(dir.into()),
+            ..self
+        }
+    }
+
+    /// Export `const` objects (`queries`, `mutations` and `subscriptions`) containing the key of every procedure.
+    pub fn path_constants(self, path_constants: bool) -> Self {
+        Self {
+            path_constants,
+            ..self
+        }
+    }
+}
+
+/// A read-only view of a procedure of a [`BuiltRouter`].
+///
+/// More fields may be added in the future so it can't be constructed outside of rspc.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct ProcedureInfo<'a> {
+    pub key: &'a str,
+    pub kind: ProcedureKind,
+    pub input: &'a DataType,
+    pub result: &'a DataType,
+    pub meta: &'a ProcedureMeta,
+}
+
+impl<'a> ProcedureInfo<'a> {
+    fn new<TCtx>(kind: ProcedureKind, key: &'a str, procedure: &'a ProcedureTodo<TCtx>) -> Self {
+        Self {
+            key,
+            kind,
+            input: &procedure.ty.input,
+            result: &procedure.ty.result,
+            meta: &procedure.meta,
+        }
+    }
+
+    /// Returns `false` if the procedure takes `()` as its input.
+    pub fn has_input(&self) -> bool {
+        // This condition is met with an empty enum or `()`.
+        !matches!(self.input, DataType::Tuple(def) if def.fields.is_empty())
+    }
 }
 
 /// BuiltRouter is a router that has been constructed and validated. It is ready to be attached to an integration to serve it to the outside world!
@@ -60,11 +127,6 @@ where
         self.typ_store.clone()
     }
 
-    #[cfg(not(feature = "unstable"))]
-    pub(crate) fn typ_store(&self) -> TypeDefs {
-        self.typ_store.clone()
-    }
-
     #[cfg(feature = "unstable")]
     pub fn queries(&self) -> &BTreeMap<String, ProcedureTodo<TCtx>> {
         &self.queries.store
@@ -80,129 +142,91 @@ where
         &self.subscriptions.store
     }
 
-    #[allow(clippy::panic_in_result_fn)] // TODO: Error handling given we return `Result`
+    pub(crate) fn store(&self, kind: ProcedureKind) -> &ProcedureStore<TCtx> {
+        match kind {
+            ProcedureKind::Query => &self.queries,
+            ProcedureKind::Mutation => &self.mutations,
+            ProcedureKind::Subscription => &self.subscriptions,
+        }
+    }
+
+    /// All procedures of the router ordered by kind and then key.
+    ///
+    /// ```rust
+    /// use rspc::Rspc;
+    ///
+    /// const R: Rspc<()> = Rspc::new();
+    ///
+    /// let router = R
+    ///     .router()
+    ///     .procedure("version", R.query(|_, _: ()| "1.0.0"))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// for procedure in router.procedures() {
+    ///     println!("{} {}", procedure.kind.to_str(), procedure.key);
+    /// }
+    /// ```
+    pub fn procedures(&self) -> impl Iterator<Item = ProcedureInfo<'_>> + '_ {
+        [
+            ProcedureKind::Query,
+            ProcedureKind::Mutation,
+            ProcedureKind::Subscription,
+        ]
+        .into_iter()
+        .flat_map(move |kind| self.procedures_of(kind))
+    }
+
+    /// The procedures of a single kind ordered by key.
+    pub fn procedures_of(
+        &self,
+        kind: ProcedureKind,
+    ) -> impl Iterator<Item = ProcedureInfo<'_>> + '_ {
+        self.store(kind)
+            .store
+            .iter()
+            .map(move |(key, p)| ProcedureInfo::new(kind, key, p))
+    }
+
+    /// Get a single procedure.
+    pub fn procedure(&self, kind: ProcedureKind, key: &str) -> Option<ProcedureInfo<'_>> {
+        self.store(kind)
+            .store
+            .get_key_value(key)
+            .map(|(key, p)| ProcedureInfo::new(kind, key, p))
+    }
+
+    /// Get the metadata attached to a procedure when it was defined.
+    pub fn procedure_meta(&self, kind: ProcedureKind, key: &str) -> Option<&ProcedureMeta> {
+        self.procedure(kind, key).map(|p| p.meta)
+    }
+
+    /// The Specta types used by the procedures. The `DataType`s of a [`ProcedureInfo`] reference them.
+    pub fn type_defs(&self) -> &TypeDefs {
+        &self.typ_store
+    }
+
     #[cfg(feature = "typescript")]
     pub fn export_ts(&self, cfg: ExportConfig) -> Result<(), ExportError> {
-        if let Some(export_dir) = cfg.export_path.parent() {
-            fs::create_dir_all(export_dir)?;
-        }
-        let mut file = File::create(cfg.export_path)?;
-        if cfg.header != "" {
-            writeln!(file, "{}", cfg.header)?;
-        }
-        writeln!(file, "// This file was generated by [rspc](https://github.com/oscartbeaumont/rspc). Do not edit this file manually.")?;
-
-        let config = ExportConfiguration::new().bigint(
-            ts::BigIntExportBehavior::FailWithReason(
-                "rspc does not support exporting bigint types (i64, u64, i128, u128) because they are lossily decoded by `JSON.parse` on the frontend. Tracking issue: https://github.com/oscartbeaumont/rspc/issues/93",
-            )
-        );
-
-        let queries_ts =
-            generate_procedures_ts(&config, self.queries.store.iter(), &self.typ_store());
-        let mutations_ts =
-            generate_procedures_ts(&config, self.mutations.store.iter(), &self.typ_store());
-        let subscriptions_ts =
-            generate_procedures_ts(&config, self.subscriptions.store.iter(), &self.typ_store());
-
-        // TODO: Specta API + `ExportConfig` option for a formatter
-        writeln!(
-            file,
-            r#"
-export type Procedures = {{
-    queries: {queries_ts},
-    mutations: {mutations_ts},
-    subscriptions: {subscriptions_ts}
-}};"#
-        )?;
-
-        // We sort by name to detect duplicate types BUT also to ensure the output is deterministic. The SID can change between builds so is not suitable for this.
-        let types = self
-            .typ_store
-            .clone()
-            .into_iter()
-            .filter(|(_, v)| match v {
-                Some(_) => true,
-                None => {
-                    unreachable!(
-                        "Placeholder type should never be returned from the Specta functions!"
-                    )
-                }
-            })
-            .collect::<BTreeMap<_, _>>();
-
-        // This is a clone of `detect_duplicate_type_names` but using a `BTreeMap` for deterministic ordering
-        let mut map = BTreeMap::new();
-        for (sid, dt) in &types {
-            match dt {
-                Some(dt) => {
-                    if let Some((existing_sid, existing_impl_location)) =
-                        map.insert(dt.name, (sid, dt.impl_location))
-                    {
-                        if existing_sid != sid {
-                            return Err(ExportError::TsExportErr(
-                                TsExportError::DuplicateTypeName(
-                                    dt.name,
-                                    dt.impl_location,
-                                    existing_impl_location,
-                                ),
-                            ));
-                        }
-                    }
-                }
-                None => unreachable!(),
+        let exporter = Typescript::new()
+            .header(cfg.header)
+            .bigint(cfg.bigint)
+            .path_constants(cfg.path_constants);
+
+        let files = match cfg.split_types {
+            Some(types_dir) => {
+                exporter.export_split(&self.export_context(), &cfg.export_path, &types_dir)?
             }
-        }
+            None => {
+                self.export(&exporter, &cfg.export_path)?;
+                vec![cfg.export_path]
+            }
+        };
 
-        for (_, (sid, _)) in map {
-            writeln!(
-                file,
-                "\n{}",
-                ts::export_datatype(
-                    &config,
-                    match types.get(sid) {
-                        Some(Some(v)) => v,
-                        _ => unreachable!(),
-                    },
-                    &types
-                )?
-            )?;
+        if let Some(formatter) = cfg.formatter {
+            run_formatter(&formatter, &files)?;
         }
 
         Ok(())
     }
 }
-
-// TODO: Move this out into a Specta API
-fn generate_procedures_ts<'a, Ctx: 'a>(
-    config: &ExportConfiguration,
-    procedures: impl ExactSizeIterator<Item = (&'a String, &'a ProcedureTodo<Ctx>)>,
-    type_store: &TypeDefs,
-) -> String {
-    match procedures.len() {
-        0 => "never".to_string(),
-        _ => procedures
-            .map(|(key, operation)| {
-                let input = match &operation.ty.input {
-                    DataType::Tuple(def)
-                        // This condition is met with an empty enum or `()`.
-                        if def.fields.is_empty() =>
-                    {
-                        "never".into()
-                    }
-                    #[allow(clippy::unwrap_used)] // TODO
-                    ty => datatype(config, ty, type_store).unwrap(),
-                };
-                #[allow(clippy::unwrap_used)] // TODO
-                let result_ts = datatype(config, &operation.ty.result, type_store).unwrap();
-
-                // TODO: Specta API
-                format!(
-                    r#"
-        {{ key: "{key}", input: {input}, result: {result_ts} }}"#
-                )
-            })
-            .collect::<Vec<_>>()
-            .join(" | "),
-    }
-}
@@ -3,7 +3,7 @@ use std::{error, fmt, sync::Arc};
 use serde::Serialize;
 use specta::{ts::TsExportError, Type};
 
-use crate::internal::exec::ResponseError;
+use crate::{internal::exec::ResponseError, ValidationError};
 
 // TODO: Context based `ExecError`. Always include the `path` of the procedure on it.
 // TODO: Cleanup this
@@ -12,10 +12,12 @@ use crate::internal::exec::ResponseError;
 pub enum ExecError {
     #[error("the requested operation is not supported by this server")]
     OperationNotFound,
-    #[error("error deserializing procedure arguments: {0}")]
-    DeserializingArgErr(serde_json::Error),
     #[error("error serializing procedure result: {0}")]
     SerializingResultErr(serde_json::Error),
+    #[error("error serializing procedure arguments: {0}")]
+    SerializingArgErr(serde_json::Error),
+    #[error("error deserializing procedure result: {0}")]
+    DeserializingResultErr(serde_json::Error),
     #[cfg(feature = "axum")]
     #[error("error in axum extractor")]
     AxumExtractorError,
@@ -33,6 +35,16 @@ pub enum ExecError {
     ErrSubscriptionsNotSupported,
     #[error("error a procedure returned an empty stream")]
     ErrStreamEmpty,
+    #[error("error the request failed CSRF validation")]
+    ErrCsrfCheckFailed,
+    #[error("error the request exceeded the maximum allowed size")]
+    ErrPayloadTooLarge,
+    #[error("{0}")]
+    ErrValidation(ValidationError),
+    #[error("error mapping the context of the procedure: {}", .0.message)]
+    ErrMappingContext(Error),
+    #[error("error parsing the requested version: {0}")]
+    ErrInvalidVersion(String),
 }
 
 impl From<ExecError> for Error {
@@ -43,14 +55,19 @@ impl From<ExecError> for Error {
                 message: "the requested operation is not supported by this server".to_string(),
                 cause: None,
             },
-            ExecError::DeserializingArgErr(err) => Error {
+            ExecError::SerializingResultErr(err) => Error {
+                code: ErrorCode::InternalServerError,
+                message: "error serializing procedure result".to_string(),
+                cause: Some(Arc::new(err)),
+            },
+            ExecError::SerializingArgErr(err) => Error {
                 code: ErrorCode::BadRequest,
-                message: "error deserializing procedure arguments".to_string(),
+                message: "error serializing procedure arguments".to_string(),
                 cause: Some(Arc::new(err)),
             },
-            ExecError::SerializingResultErr(err) => Error {
+            ExecError::DeserializingResultErr(err) => Error {
                 code: ErrorCode::InternalServerError,
-                message: "error serializing procedure result".to_string(),
+                message: "error deserializing procedure result".to_string(),
                 cause: Some(Arc::new(err)),
             },
             #[cfg(feature = "axum")]
@@ -85,6 +102,27 @@ impl From<ExecError> for Error {
                 message: "error a procedure returned an empty stream".into(),
                 cause: None,
             },
+            ExecError::ErrCsrfCheckFailed => Error {
+                code: ErrorCode::Forbidden,
+                message: "error the request failed CSRF validation".into(),
+                cause: None,
+            },
+            ExecError::ErrPayloadTooLarge => Error {
+                code: ErrorCode::PayloadTooLarge,
+                message: "error the request exceeded the maximum allowed size".into(),
+                cause: None,
+            },
+            ExecError::ErrValidation(err) => Error {
+                code: ErrorCode::BadRequest,
+                message: "error validating procedure arguments".into(),
+                cause: Some(Arc::new(err)),
+            },
+            ExecError::ErrMappingContext(err) => err,
+            ExecError::ErrInvalidVersion(msg) => Error {
+                code: ErrorCode::BadRequest,
+                message: format!("error parsing the requested version: {msg}"),
+                cause: None,
+            },
         }
     }
 }
@@ -101,8 +139,9 @@ impl From<ExecError> for ResponseError {
         Self {
             code: match &err {
                 ExecError::OperationNotFound => ErrorCode::NotFound,
-                ExecError::DeserializingArgErr(_) => ErrorCode::BadRequest,
                 ExecError::SerializingResultErr(_) => ErrorCode::InternalServerError,
+                ExecError::SerializingArgErr(_) => ErrorCode::BadRequest,
+                ExecError::DeserializingResultErr(_) => ErrorCode::InternalServerError,
                 #[cfg(feature = "axum")]
                 ExecError::AxumExtractorError => ErrorCode::BadRequest,
                 ExecError::ErrResolverError(err) => err.code,
@@ -110,11 +149,20 @@ impl From<ExecError> for ResponseError {
                 ExecError::ErrSubscriptionDuplicateId => ErrorCode::BadRequest,
                 ExecError::ErrSubscriptionsNotSupported => ErrorCode::BadRequest,
                 ExecError::ErrStreamEmpty => ErrorCode::InternalServerError,
+                ExecError::ErrCsrfCheckFailed => ErrorCode::Forbidden,
+                ExecError::ErrPayloadTooLarge => ErrorCode::PayloadTooLarge,
+                ExecError::ErrValidation(_) => ErrorCode::BadRequest,
+                ExecError::ErrMappingContext(err) => err.code,
+                ExecError::ErrInvalidVersion(_) => ErrorCode::BadRequest,
             }
             .to_status_code(),
+            data: match &err {
+                // This includes arguments which failed to deserialize so the frontend knows which field was invalid.
+                ExecError::ErrValidation(err) => serde_json::to_value(err).ok(),
+                _ => None,
+            },
             // TODO: Don't expose the error to the frontend by default
             message: err.to_string(),
-            data: None,
         }
     }
 }
@@ -125,6 +173,10 @@ pub enum ExportError {
     IOErr(#[from] std::io::Error),
     #[error("error exporting typescript bindings: {0}")]
     TsExportErr(#[from] TsExportError),
+    #[error("error running formatter on bindings: {0}")]
+    FormatterErr(String),
+    #[error("error exporting path constants: `{1}` and `{2}` are both exported as `{0}`")]
+    DuplicatePathConstant(String, String, String),
 }
 
 #[derive(Debug, Clone, Serialize, Type)]
@@ -171,6 +223,14 @@ impl Error {
             cause: Some(Arc::new(cause)),
         }
     }
+
+    pub fn code(&self) -> ErrorCode {
+        self.code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
 }
 
 /// TODO
@@ -0,0 +1,82 @@
+//! Serialize integers as strings so they can be decoded by `JSON.parse` without losing precision.
+//!
+//! This is the server side of [`BigIntExport::String`](super::BigIntExport::String) and [`BigIntExport::BigInt`](super::BigIntExport::BigInt) which require every `i64`, `u64`, `i128` and `u128` used by your procedures to be annotated.
+//! Numbers are also accepted when deserializing so older clients keep working.
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, specta::Type)]
+//! struct File {
+//!     #[serde(with = "rspc::export::bigint")]
+//!     size: u64,
+//! }
+//! ```
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    de::{self, Visitor},
+    Deserializer, Serializer,
+};
+
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_any(BigIntVisitor(PhantomData))
+}
+
+struct BigIntVisitor<T>(PhantomData<T>);
+
+impl<T> BigIntVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn parse<E: de::Error>(v: &str) -> Result<T, E> {
+        v.parse().map_err(E::custom)
+    }
+}
+
+impl<'de, T> Visitor<'de> for BigIntVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an integer or a string containing an integer")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        Self::parse(v)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        Self::parse(&v.to_string())
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        Self::parse(&v.to_string())
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<T, E> {
+        Self::parse(&v.to_string())
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<T, E> {
+        Self::parse(&v.to_string())
+    }
+}
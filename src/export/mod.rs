@@ -0,0 +1,346 @@
+//! Generate bindings so your rspc router can be called with type safety from other languages.
+//!
+//! An [`Exporter`] is handed the procedures and types of a [`BuiltRouter`] and returns the source of the bindings.
+//! rspc comes with exporters for [`Typescript`], [`Python`], [`Swift`] and [`Rust`] but you can implement the trait yourself for any other language.
+
+pub mod bigint;
+
+mod python;
+mod rust;
+mod swift;
+#[cfg(feature = "typescript")]
+mod typescript;
+
+pub use python::*;
+pub use rust::*;
+pub use swift::*;
+#[cfg(feature = "typescript")]
+pub use typescript::*;
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Write,
+    path::PathBuf,
+};
+
+use specta::{
+    ts::TsExportError, DataType, DataTypeReference, EnumType, EnumVariant, NamedDataType,
+    NamedDataTypeItem, ObjectType, TupleType, TypeDefs,
+};
+
+use crate::{internal::middleware::ProcedureKind, BuiltRouter, ExportError, ProcedureInfo};
+
+/// How 64 and 128 bit integers (`i64`, `u64`, `i128`, `u128`) are exported to Typescript.
+///
+/// These can't be represented by a Javascript `number` without losing precision once they are larger than `Number.MAX_SAFE_INTEGER`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BigIntExport {
+    /// Fail to export the bindings if one of these types is used.
+    #[default]
+    Fail,
+    /// Export the types as `string`.
+    ///
+    /// Specta can't see how a field is serialized so **every** one of these integers used by your procedures must be annotated with `#[serde(with = "rspc::export::bigint")]`.
+    /// Unannotated fields are still serialized as numbers and won't match the exported type.
+    String,
+    /// Export the types as `number`. Values larger than `Number.MAX_SAFE_INTEGER` will be rounded by `JSON.parse` on the frontend.
+    Number,
+    /// Export the types as `BigInt`, the interface of a `bigint` in Typescript.
+    ///
+    /// `JSON.parse` can't produce a `bigint` so these integers are sent as strings like with [`BigIntExport::String`] and every one of them must be annotated with `#[serde(with = "rspc::export::bigint")]`.
+    /// Convert the strings with `BigInt(value)` on the frontend and send them back with `value.toString()` as `JSON.stringify` throws on a `bigint`.
+    BigInt,
+}
+
+/// Generate the bindings for a router in a specific language.
+pub trait Exporter {
+    fn export(&self, ctx: &ExportContext<'_>) -> Result<String, ExportError>;
+}
+
+/// The procedures and types of a router which is being exported.
+pub struct ExportContext<'a> {
+    procedures: Vec<ProcedureInfo<'a>>,
+    types: &'a TypeDefs,
+}
+
+impl<'a> ExportContext<'a> {
+    /// All procedures of the router ordered by kind and then key.
+    pub fn procedures(&self) -> &[ProcedureInfo<'a>] {
+        &self.procedures
+    }
+
+    /// The procedures of a single kind ordered by key.
+    pub fn procedures_of(
+        &self,
+        kind: ProcedureKind,
+    ) -> impl Iterator<Item = &ProcedureInfo<'a>> + Clone + '_ {
+        self.procedures.iter().filter(move |p| p.kind == kind)
+    }
+
+    /// The raw Specta type map used to resolve references.
+    pub fn type_defs(&self) -> &'a TypeDefs {
+        self.types
+    }
+
+    /// Every named type used by the router sorted by name.
+    ///
+    /// Returns an error if two different types share the same name as they would collide in the bindings.
+    // We sort by name to detect duplicate types BUT also to ensure the output is deterministic. The SID can change between builds so is not suitable for this.
+    pub fn types(&self) -> Result<Vec<&'a NamedDataType>, ExportError> {
+        let mut map = BTreeMap::new();
+        for (sid, dt) in self.types.iter() {
+            let Some(dt) = dt else {
+                continue;
+            };
+
+            if let Some((existing_sid, existing)) = map.insert(dt.name, (sid, dt)) {
+                if existing_sid != sid {
+                    return Err(ExportError::TsExportErr(TsExportError::DuplicateTypeName(
+                        dt.name,
+                        dt.impl_location,
+                        existing.impl_location,
+                    )));
+                }
+            }
+        }
+
+        Ok(map.into_values().map(|(_, dt)| dt).collect())
+    }
+}
+
+impl<TCtx> BuiltRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    /// Collect the procedures and types of the router for an [`Exporter`].
+    pub fn export_context(&self) -> ExportContext<'_> {
+        ExportContext {
+            procedures: self.procedures().collect(),
+            types: &self.typ_store,
+        }
+    }
+
+    /// Generate bindings using `exporter` and return them as a string.
+    pub fn export_to_string(&self, exporter: &impl Exporter) -> Result<String, ExportError> {
+        exporter.export(&self.export_context())
+    }
+
+    /// Generate bindings using `exporter` and write them to a file.
+    ///
+    /// ```rust,no_run
+    /// use rspc::{export::Swift, Rspc};
+    ///
+    /// const R: Rspc<()> = Rspc::new();
+    ///
+    /// let router = R
+    ///     .router()
+    ///     .procedure("version", R.query(|_, _: ()| "1.0.0"))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// router.export(&Swift::new(), "./ios/Bindings.swift").unwrap();
+    /// ```
+    pub fn export(
+        &self,
+        exporter: &impl Exporter,
+        export_path: impl Into<PathBuf>,
+    ) -> Result<(), ExportError> {
+        let export_path = export_path.into();
+        if let Some(export_dir) = export_path.parent() {
+            fs::create_dir_all(export_dir)?;
+        }
+
+        let bindings = self.export_to_string(exporter)?;
+        File::create(export_path)?.write_all(bindings.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Run a formatter command such as `prettier --write` on the exported files.
+#[cfg(feature = "typescript")]
+pub(crate) fn run_formatter(command: &str, files: &[PathBuf]) -> Result<(), ExportError> {
+    use std::{path::Path, process::Command};
+
+    let mut args = split_command(command)?.into_iter();
+    let Some(program) = args.next() else {
+        return Ok(());
+    };
+
+    let output = Command::new(program)
+        .args(args)
+        .args(files.iter().map(PathBuf::as_path).map(Path::as_os_str))
+        .output()?;
+
+    match output.status.success() {
+        true => Ok(()),
+        false => Err(ExportError::FormatterErr(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// Split a command into its arguments like a shell would. Arguments can be quoted with `'` or `"` and `\` escapes the next character outside of single quotes.
+#[cfg(feature = "typescript")]
+fn split_command(command: &str) -> Result<Vec<String>, ExportError> {
+    let mut args = Vec::new();
+    let mut arg = None::<String>;
+    let mut quote = None;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                arg.get_or_insert_with(String::new);
+            }
+            (None | Some('"'), '\\') => {
+                let c = chars.next().ok_or_else(|| {
+                    ExportError::FormatterErr(format!("command `{command}` ends with a `\\`"))
+                })?;
+                arg.get_or_insert_with(String::new).push(c);
+            }
+            (None, c) if c.is_whitespace() => args.extend(arg.take()),
+            (_, c) => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if quote.is_some() {
+        return Err(ExportError::FormatterErr(format!(
+            "command `{command}` has an unterminated quote"
+        )));
+    }
+    args.extend(arg);
+
+    Ok(args)
+}
+
+/// Call `f` with every reference to a named type in `ty`. The definitions of the referenced types are not walked.
+pub(crate) fn walk_references(ty: &DataType, f: &mut impl FnMut(&DataTypeReference)) {
+    match ty {
+        DataType::List(ty) | DataType::Nullable(ty) => walk_references(ty, f),
+        DataType::Record(ty) => {
+            walk_references(&ty.0, f);
+            walk_references(&ty.1, f);
+        }
+        DataType::Named(ty) => walk_item_references(&ty.item, f),
+        DataType::Object(ty) => walk_object_references(ty, f),
+        DataType::Enum(ty) => walk_enum_references(ty, f),
+        DataType::Tuple(ty) => walk_tuple_references(ty, f),
+        DataType::Reference(ty) => {
+            f(ty);
+            for generic in &ty.generics {
+                walk_references(generic, f);
+            }
+        }
+        DataType::Any | DataType::Primitive(_) | DataType::Literal(_) | DataType::Generic(_) => {}
+    }
+}
+
+/// Call `f` with every reference to a named type in the definition of a named type.
+pub(crate) fn walk_item_references(
+    item: &NamedDataTypeItem,
+    f: &mut impl FnMut(&DataTypeReference),
+) {
+    match item {
+        NamedDataTypeItem::Object(ty) => walk_object_references(ty, f),
+        NamedDataTypeItem::Enum(ty) => walk_enum_references(ty, f),
+        NamedDataTypeItem::Tuple(ty) => walk_tuple_references(ty, f),
+    }
+}
+
+fn walk_object_references(ty: &ObjectType, f: &mut impl FnMut(&DataTypeReference)) {
+    for field in &ty.fields {
+        walk_references(&field.ty, f);
+    }
+}
+
+fn walk_tuple_references(ty: &TupleType, f: &mut impl FnMut(&DataTypeReference)) {
+    for ty in &ty.fields {
+        walk_references(ty, f);
+    }
+}
+
+fn walk_enum_references(ty: &EnumType, f: &mut impl FnMut(&DataTypeReference)) {
+    let variants: Vec<&EnumVariant> = match ty {
+        EnumType::Untagged { variants, .. } => variants.iter().collect(),
+        EnumType::Tagged { variants, .. } => variants.iter().map(|(_, v)| v).collect(),
+    };
+
+    for variant in variants {
+        match variant {
+            EnumVariant::Unit => {}
+            EnumVariant::Unnamed(ty) => walk_tuple_references(ty, f),
+            EnumVariant::Named(ty) => walk_object_references(ty, f),
+        }
+    }
+}
+
+/// Convert a procedure key or field name into an identifier in `snake_case`. Any characters which aren't valid in an identifier become `_`.
+pub(crate) fn to_snake_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+            prev_lower = false;
+        } else if c.is_alphanumeric() {
+            result.push(c);
+            prev_lower = c.is_lowercase() || c.is_numeric();
+        } else {
+            result.push('_');
+            prev_lower = false;
+        }
+    }
+    result
+}
+
+/// Convert a procedure key or variant name into an identifier in `PascalCase`. Any characters which aren't valid in an identifier are removed.
+pub(crate) fn to_pascal_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut upper = true;
+    for c in s.chars() {
+        if !c.is_alphanumeric() {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Convert a procedure key or variant name into an identifier in `camelCase`. Any characters which aren't valid in an identifier are removed.
+pub(crate) fn to_camel_case(s: &str) -> String {
+    let s = to_pascal_case(s);
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) => c.to_lowercase().chain(chars).collect(),
+        None => s,
+    }
+}
+
+#[cfg(all(test, feature = "typescript"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        assert_eq!(
+            split_command("prettier  --write").unwrap(),
+            ["prettier", "--write"]
+        );
+        assert_eq!(
+            split_command(r#"npx "my formatter" --config 'a b.json' --x="" c\ d"#).unwrap(),
+            ["npx", "my formatter", "--config", "a b.json", "--x=", "c d"]
+        );
+        assert!(split_command("").unwrap().is_empty());
+        assert!(split_command("prettier 'unterminated").is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use std::borrow::Cow;
+
+use specta::{
+    DataType, EnumRepr, EnumType, EnumVariant, LiteralType, NamedDataType, NamedDataTypeItem,
+    ObjectType, PrimitiveType, TypeDefs,
+};
+
+use crate::{internal::middleware::ProcedureKind, ExportError};
+
+use super::{to_pascal_case, to_snake_case, ExportContext, Exporter};
+
+/// Export Python bindings. Types are exported as `TypedDict`s and a `Client` is generated for calling queries and mutations over HTTP.
+///
+/// The bindings require Python 3.11 or newer and have no dependencies outside of the standard library.
+#[derive(Debug, Clone, Default)]
+pub struct Python {
+    header: Cow<'static, str>,
+}
+
+impl Python {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string to the top of the generated file. Eg. to disable a linter.
+    pub fn header(self, header: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header: header.into(),
+        }
+    }
+}
+
+impl Exporter for Python {
+    fn export(&self, ctx: &ExportContext<'_>) -> Result<String, ExportError> {
+        let mut out = String::new();
+        if !self.header.is_empty() {
+            out.push_str(&self.header);
+            out.push('\n');
+        }
+        out.push_str(PRELUDE);
+
+        for ty in ctx.types()? {
+            out.push_str("\n\n");
+            out.push_str(&named_datatype(ty, ctx.type_defs()));
+        }
+
+        out.push_str(CLIENT);
+        for procedure in ctx.procedures() {
+            let method = match procedure.kind {
+                ProcedureKind::Query => "GET",
+                ProcedureKind::Mutation => "POST",
+                // Subscriptions require a websocket which the generated client doesn't support.
+                ProcedureKind::Subscription => continue,
+            };
+            let name = format!(
+                "{}_{}",
+                match procedure.kind {
+                    ProcedureKind::Query => "query",
+                    _ => "mutation",
+                },
+                to_snake_case(procedure.key)
+            );
+            let (arg, input) = match procedure.has_input() {
+                true => (format!(", input: {}", datatype(procedure.input)), "input"),
+                false => (String::new(), "None"),
+            };
+
+            out.push_str(&format!(
+                "\n    def {name}(self{arg}) -> {}:\n",
+                datatype(procedure.result)
+            ));
+            if let Some(description) = procedure.meta.description() {
+                out.push_str(&format!(
+                    "        \"\"\"{}\"\"\"\n",
+                    description.replace("\"\"\"", "\\\"\\\"\\\"")
+                ));
+            }
+            out.push_str(&format!(
+                "        return self._call(\"{method}\", \"{}\", {input})\n",
+                procedure.key
+            ));
+        }
+
+        Ok(out)
+    }
+}
+
+const PRELUDE: &str = r#"# This file was generated by [rspc](https://github.com/oscartbeaumont/rspc). Do not edit this file manually.
+from __future__ import annotations
+
+import json
+import urllib.error
+import urllib.parse
+import urllib.request
+from typing import Any, Dict, List, Literal, NotRequired, Optional, Tuple, TypeAlias, TypedDict, Union
+"#;
+
+const CLIENT: &str = r#"
+
+
+class RspcError(Exception):
+    def __init__(self, code: int, message: str, data: Any = None) -> None:
+        super().__init__(message)
+        self.code = code
+        self.message = message
+        self.data = data
+
+
+class Client:
+    """Call the queries and mutations of the rspc router over HTTP."""
+
+    def __init__(self, url: str, headers: Optional[Dict[str, str]] = None) -> None:
+        self.url = url.rstrip("/")
+        self.headers = headers or {}
+
+    def _call(self, method: str, key: str, input: Any) -> Any:
+        url = f"{self.url}/{key}"
+        data = None
+        if method == "GET":
+            if input is not None:
+                url += "?" + urllib.parse.urlencode({"input": json.dumps(input)})
+        elif input is not None:
+            data = json.dumps(input).encode()
+
+        req = urllib.request.Request(
+            url,
+            data=data,
+            method=method,
+            headers={"Content-Type": "application/json", **self.headers},
+        )
+        try:
+            with urllib.request.urlopen(req) as resp:
+                body = json.load(resp)
+        except urllib.error.HTTPError as e:
+            body = json.load(e)
+
+        if body["type"] == "error":
+            err = body["value"]
+            raise RspcError(err["code"], err["message"], err.get("data"))
+        return body["value"]
+"#;
+
+fn named_datatype(ty: &NamedDataType, type_defs: &TypeDefs) -> String {
+    let name = ty.name;
+    let mut out = String::new();
+    for comment in ty.comments {
+        out.push_str(&format!("#{comment}\n"));
+    }
+
+    match &ty.item {
+        NamedDataTypeItem::Object(obj) => out.push_str(&typed_dict(name, &fields(obj, type_defs))),
+        NamedDataTypeItem::Tuple(tuple) => out.push_str(&format!(
+            "{name}: TypeAlias = {}",
+            datatype(&DataType::Tuple(tuple.clone()))
+        )),
+        NamedDataTypeItem::Enum(e) => {
+            let mut helpers = Vec::new();
+            let variants = enum_variants(name, e, type_defs, &mut helpers);
+            for helper in helpers {
+                out.push_str(&helper);
+                out.push('\n');
+            }
+            out.push_str(&match &variants[..] {
+                [] => format!("{name}: TypeAlias = None"),
+                [ty] => format!("{name}: TypeAlias = {ty}"),
+                tys => format!("{name}: TypeAlias = Union[{}]", tys.join(", ")),
+            });
+        }
+    }
+
+    out
+}
+
+/// The fields of an object as `(key, type)`. Flattened objects are inlined.
+fn fields(obj: &ObjectType, type_defs: &TypeDefs) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    for field in &obj.fields {
+        if field.flatten {
+            let inner = match &field.ty {
+                DataType::Object(obj) => Some(obj),
+                DataType::Reference(r) => match type_defs.get(&r.sid) {
+                    Some(Some(NamedDataType {
+                        item: NamedDataTypeItem::Object(obj),
+                        ..
+                    })) => Some(obj),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some(inner) = inner {
+                fields.extend(self::fields(inner, type_defs));
+            }
+            continue;
+        }
+
+        let ty = datatype(&field.ty);
+        fields.push((
+            field.key.to_string(),
+            match field.optional {
+                true => format!("NotRequired[{ty}]"),
+                false => ty,
+            },
+        ));
+    }
+    fields
+}
+
+// The functional syntax is used as the keys may not be valid Python identifiers.
+fn typed_dict(name: &str, fields: &[(String, String)]) -> String {
+    let fields = fields
+        .iter()
+        .map(|(key, ty)| format!("{}: {ty}", string(key)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("{name} = TypedDict(\"{name}\", {{{fields}}})")
+}
+
+fn enum_variants(
+    name: &str,
+    e: &EnumType,
+    type_defs: &TypeDefs,
+    helpers: &mut Vec<String>,
+) -> Vec<String> {
+    match e {
+        EnumType::Untagged { variants, .. } => variants
+            .iter()
+            .map(|v| match v {
+                EnumVariant::Unit => "None".to_string(),
+                EnumVariant::Unnamed(tuple) => datatype(&DataType::Tuple(tuple.clone())),
+                EnumVariant::Named(_) => "Dict[str, Any]".to_string(),
+            })
+            .collect(),
+        EnumType::Tagged { variants, repr, .. } => variants
+            .iter()
+            .map(|(variant_name, v)| {
+                let helper_name = format!("{name}{}", to_pascal_case(variant_name));
+                let literal = format!("Literal[{}]", string(variant_name));
+
+                // The type of the variant's data, creating a helper for inline objects.
+                let payload = |helpers: &mut Vec<String>| match v {
+                    EnumVariant::Unit => None,
+                    EnumVariant::Unnamed(tuple) => Some(datatype(&DataType::Tuple(tuple.clone()))),
+                    EnumVariant::Named(obj) => {
+                        let data_name = format!("{helper_name}Data");
+                        helpers.push(typed_dict(&data_name, &fields(obj, type_defs)));
+                        Some(string(&data_name))
+                    }
+                };
+
+                match repr {
+                    EnumRepr::External => match payload(helpers) {
+                        None => literal,
+                        Some(payload) => {
+                            helpers.push(typed_dict(
+                                &helper_name,
+                                &[(variant_name.to_string(), payload)],
+                            ));
+                            string(&helper_name)
+                        }
+                    },
+                    EnumRepr::Internal { tag } => {
+                        let mut f = vec![(tag.to_string(), literal)];
+                        match v {
+                            EnumVariant::Named(obj) => f.extend(fields(obj, type_defs)),
+                            EnumVariant::Unnamed(_) => return "Dict[str, Any]".to_string(),
+                            EnumVariant::Unit => {}
+                        }
+                        helpers.push(typed_dict(&helper_name, &f));
+                        string(&helper_name)
+                    }
+                    EnumRepr::Adjacent { tag, content } => {
+                        let mut f = vec![(tag.to_string(), literal)];
+                        if let Some(payload) = payload(helpers) {
+                            f.push((content.to_string(), payload));
+                        }
+                        helpers.push(typed_dict(&helper_name, &f));
+                        string(&helper_name)
+                    }
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Convert a [`DataType`] into a Python type annotation. References to other types are quoted so they can be used before they are defined.
+fn datatype(ty: &DataType) -> String {
+    match ty {
+        DataType::Any | DataType::Generic(_) => "Any".into(),
+        DataType::Primitive(ty) => match ty {
+            PrimitiveType::f32 | PrimitiveType::f64 => "float",
+            PrimitiveType::bool => "bool",
+            PrimitiveType::char | PrimitiveType::String => "str",
+            _ => "int",
+        }
+        .into(),
+        DataType::Literal(ty) => match ty {
+            LiteralType::i8(v) => format!("Literal[{v}]"),
+            LiteralType::i16(v) => format!("Literal[{v}]"),
+            LiteralType::i32(v) => format!("Literal[{v}]"),
+            LiteralType::u8(v) => format!("Literal[{v}]"),
+            LiteralType::u16(v) => format!("Literal[{v}]"),
+            LiteralType::u32(v) => format!("Literal[{v}]"),
+            LiteralType::f32(_) | LiteralType::f64(_) => "float".into(),
+            LiteralType::bool(true) => "Literal[True]".into(),
+            LiteralType::bool(false) => "Literal[False]".into(),
+            LiteralType::String(v) => format!("Literal[{}]", string(v)),
+            LiteralType::None => "None".into(),
+        },
+        DataType::List(ty) => format!("List[{}]", datatype(ty)),
+        DataType::Nullable(ty) => format!("Optional[{}]", datatype(ty)),
+        DataType::Record(ty) => format!("Dict[str, {}]", datatype(&ty.1)),
+        DataType::Named(ty) => string(ty.name),
+        DataType::Object(_) => "Dict[str, Any]".into(),
+        DataType::Enum(_) => "Any".into(),
+        DataType::Tuple(ty) => match &ty.fields[..] {
+            [] => "None".into(),
+            [ty] => datatype(ty),
+            fields => format!(
+                "Tuple[{}]",
+                fields.iter().map(datatype).collect::<Vec<_>>().join(", ")
+            ),
+        },
+        DataType::Reference(ty) => string(ty.name),
+    }
+}
+
+fn string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| format!("\"{s}\""))
+}
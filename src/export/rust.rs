@@ -0,0 +1,330 @@
+use std::borrow::Cow;
+
+use specta::{
+    DataType, EnumRepr, EnumType, EnumVariant, LiteralType, NamedDataType, NamedDataTypeItem,
+    ObjectType, PrimitiveType,
+};
+
+use crate::{internal::middleware::ProcedureKind, ExportError};
+
+use super::{to_pascal_case, to_snake_case, ExportContext, Exporter};
+
+/// Export Rust bindings. Types are exported as `serde` structs and enums and a typed function is generated for every procedure which calls it using the `rspc-client` crate.
+///
+/// The bindings depend on `rspc-client`, `serde` and `serde_json`. Queries and mutations are called over HTTP and subscriptions over a websocket.
+///
+/// ```rust,ignore
+/// mod bindings; // Exported with `router.export(&Rust::new(), "./src/bindings.rs")`
+///
+/// let client = rspc_client::Client::new("http://localhost:4000/rspc");
+/// let user = bindings::queries::users_get(&client, &1).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Rust {
+    header: Cow<'static, str>,
+}
+
+impl Rust {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string to the top of the generated file. Eg. to disable a lint.
+    pub fn header(self, header: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header: header.into(),
+        }
+    }
+}
+
+impl Exporter for Rust {
+    fn export(&self, ctx: &ExportContext<'_>) -> Result<String, ExportError> {
+        let mut out = String::new();
+        if !self.header.is_empty() {
+            out.push_str(&self.header);
+            out.push('\n');
+        }
+        out.push_str(PRELUDE);
+
+        for ty in ctx.types()? {
+            out.push('\n');
+            out.push_str(&named_datatype(ty));
+        }
+
+        for (kind, module, method) in [
+            (ProcedureKind::Query, "queries", "query"),
+            (ProcedureKind::Mutation, "mutations", "mutation"),
+            (ProcedureKind::Subscription, "subscriptions", "subscription"),
+        ] {
+            let mut procedures = ctx.procedures_of(kind).peekable();
+            if procedures.peek().is_none() {
+                continue;
+            }
+
+            out.push_str(&format!("\npub mod {module} {{\n    use super::*;\n"));
+            for procedure in procedures {
+                let (arg, input) = match procedure.has_input() {
+                    true => (format!(", input: &{}", datatype(procedure.input)), "input"),
+                    false => (String::new(), "&()"),
+                };
+                let result = match kind {
+                    ProcedureKind::Subscription => format!(
+                        "::rspc_client::Subscription<{}>",
+                        datatype(procedure.result)
+                    ),
+                    _ => datatype(procedure.result),
+                };
+
+                out.push('\n');
+                if let Some(description) = procedure.meta.description() {
+                    for line in description.lines() {
+                        out.push_str(&format!("    /// {line}\n"));
+                    }
+                }
+                match procedure.meta.deprecated() {
+                    Some("") => out.push_str("    #[deprecated]\n"),
+                    Some(reason) => {
+                        out.push_str(&format!("    #[deprecated = {}]\n", string(reason)))
+                    }
+                    None => {}
+                }
+                out.push_str(&format!(
+                    "    pub async fn {}(client: &::rspc_client::Client{arg}) -> Result<{result}, ::rspc_client::Error> {{\n        client.{method}({}, {input}).await\n    }}\n",
+                    ident(&to_snake_case(procedure.key)),
+                    string(procedure.key)
+                ));
+            }
+            out.push_str("}\n");
+        }
+
+        Ok(out)
+    }
+}
+
+const PRELUDE: &str = r#"// This file was generated by [rspc](https://github.com/oscartbeaumont/rspc). Do not edit this file manually.
+#![allow(dead_code, deprecated, clippy::all)]
+"#;
+
+const DERIVE: &str =
+    "#[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]\n";
+
+fn named_datatype(ty: &NamedDataType) -> String {
+    let name = ty.name;
+    let mut out = String::new();
+    for comment in ty.comments {
+        out.push_str(&format!("///{comment}\n"));
+    }
+    out.push_str(DERIVE);
+
+    match &ty.item {
+        NamedDataTypeItem::Object(obj) => {
+            out.push_str(&format!("pub struct {name} {{\n"));
+            out.push_str(&fields(name, obj, "pub "));
+            out.push_str("}\n");
+        }
+        NamedDataTypeItem::Tuple(tuple) => match &tuple.fields[..] {
+            [] => out.push_str(&format!("pub struct {name};\n")),
+            fields => out.push_str(&format!(
+                "pub struct {name}({});\n",
+                fields
+                    .iter()
+                    .map(|ty| format!("pub {}", boxed(name, datatype(ty))))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        },
+        NamedDataTypeItem::Enum(e) => out.push_str(&enumeration(name, e)),
+    }
+
+    out
+}
+
+/// The fields of a struct or struct variant. `parent` is the name of the type the fields belong to so recursive fields can be boxed.
+fn fields(parent: &str, obj: &ObjectType, vis: &str) -> String {
+    let mut out = String::new();
+    for field in &obj.fields {
+        let name = ident(&to_snake_case(field.key));
+        let mut ty = boxed(parent, datatype(&field.ty));
+
+        let mut attrs = Vec::new();
+        if name.trim_start_matches("r#") != field.key {
+            attrs.push(format!("rename = {}", string(field.key)));
+        }
+        if field.flatten {
+            attrs.push("flatten".into());
+        } else if field.optional {
+            attrs.push("default, skip_serializing_if = \"Option::is_none\"".into());
+            if !ty.starts_with("Option<") {
+                ty = format!("Option<{ty}>");
+            }
+        }
+
+        if !attrs.is_empty() {
+            out.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+        }
+        out.push_str(&format!("    {vis}{name}: {ty},\n"));
+    }
+    out
+}
+
+fn enumeration(name: &str, e: &EnumType) -> String {
+    // The Rust name, the serialized name and the data of each variant.
+    let (attr, variants) = match e {
+        EnumType::Untagged { variants, .. } => (
+            Some("untagged".to_string()),
+            variants
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (format!("Variant{i}"), None, v))
+                .collect::<Vec<_>>(),
+        ),
+        EnumType::Tagged { variants, repr, .. } => (
+            match repr {
+                EnumRepr::External => None,
+                EnumRepr::Internal { tag } => Some(format!("tag = {}", string(tag))),
+                EnumRepr::Adjacent { tag, content } => Some(format!(
+                    "tag = {}, content = {}",
+                    string(tag),
+                    string(content)
+                )),
+            },
+            variants
+                .iter()
+                .map(|(n, v)| (ident(&to_pascal_case(n)), Some(&**n), v))
+                .collect(),
+        ),
+    };
+
+    let mut out = String::new();
+    if let Some(attr) = attr {
+        out.push_str(&format!("#[serde({attr})]\n"));
+    }
+    out.push_str(&format!("pub enum {name} {{\n"));
+    for (variant, serialized, v) in variants {
+        if let Some(serialized) = serialized.filter(|s| variant.trim_start_matches("r#") != *s) {
+            out.push_str(&format!("    #[serde(rename = {})]\n", string(serialized)));
+        }
+
+        match v {
+            EnumVariant::Unit => out.push_str(&format!("    {variant},\n")),
+            EnumVariant::Unnamed(tuple) => out.push_str(&format!(
+                "    {variant}({}),\n",
+                tuple
+                    .fields
+                    .iter()
+                    .map(|ty| boxed(name, datatype(ty)))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            EnumVariant::Named(obj) => {
+                let fields = fields(name, obj, "");
+                out.push_str(&format!("    {variant} {{\n"));
+                for line in fields.lines() {
+                    out.push_str(&format!("    {line}\n"));
+                }
+                out.push_str("    },\n");
+            }
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Box a type which directly contains itself as it would otherwise have an infinite size.
+fn boxed(parent: &str, ty: String) -> String {
+    match ty
+        .strip_prefix("Option<")
+        .and_then(|ty| ty.strip_suffix('>'))
+    {
+        Some(inner) if inner == parent => format!("Option<Box<{inner}>>"),
+        _ if ty == parent => format!("Box<{ty}>"),
+        _ => ty,
+    }
+}
+
+/// Convert a [`DataType`] into a Rust type. Types which can't be represented are exported as `serde_json::Value`.
+fn datatype(ty: &DataType) -> String {
+    match ty {
+        DataType::Any | DataType::Generic(_) | DataType::Object(_) | DataType::Enum(_) => {
+            "::serde_json::Value".into()
+        }
+        DataType::Primitive(ty) => match ty {
+            PrimitiveType::i8 => "i8",
+            PrimitiveType::i16 => "i16",
+            PrimitiveType::i32 => "i32",
+            PrimitiveType::i64 => "i64",
+            PrimitiveType::i128 => "i128",
+            PrimitiveType::isize => "isize",
+            PrimitiveType::u8 => "u8",
+            PrimitiveType::u16 => "u16",
+            PrimitiveType::u32 => "u32",
+            PrimitiveType::u64 => "u64",
+            PrimitiveType::u128 => "u128",
+            PrimitiveType::usize => "usize",
+            PrimitiveType::f32 => "f32",
+            PrimitiveType::f64 => "f64",
+            PrimitiveType::bool => "bool",
+            PrimitiveType::char => "char",
+            PrimitiveType::String => "String",
+        }
+        .into(),
+        DataType::Literal(ty) => match ty {
+            LiteralType::i8(_) => "i8",
+            LiteralType::i16(_) => "i16",
+            LiteralType::i32(_) => "i32",
+            LiteralType::u8(_) => "u8",
+            LiteralType::u16(_) => "u16",
+            LiteralType::u32(_) => "u32",
+            LiteralType::f32(_) => "f32",
+            LiteralType::f64(_) => "f64",
+            LiteralType::bool(_) => "bool",
+            LiteralType::String(_) => "String",
+            LiteralType::None => "()",
+        }
+        .into(),
+        DataType::List(ty) => format!("Vec<{}>", datatype(ty)),
+        DataType::Nullable(ty) => match datatype(ty) {
+            ty if ty.starts_with("Option<") => ty,
+            ty => format!("Option<{ty}>"),
+        },
+        // JSON object keys are always strings.
+        DataType::Record(ty) => format!("::std::collections::HashMap<String, {}>", datatype(&ty.1)),
+        DataType::Named(ty) => ty.name.into(),
+        DataType::Tuple(ty) => match &ty.fields[..] {
+            [] => "()".into(),
+            [ty] => format!("({},)", datatype(ty)),
+            fields => format!(
+                "({})",
+                fields.iter().map(datatype).collect::<Vec<_>>().join(", ")
+            ),
+        },
+        DataType::Reference(ty) => ty.name.into(),
+    }
+}
+
+/// Convert a name into a valid identifier, using a raw identifier for keywords.
+fn ident(name: &str) -> String {
+    let name = match name.is_empty() || name.starts_with(|c: char| c.is_numeric()) {
+        true => format!("_{name}"),
+        false => name.to_string(),
+    };
+
+    match name.as_str() {
+        // These can't be raw identifiers
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        n if KEYWORDS.contains(&n) => format!("r#{n}"),
+        _ => name,
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+fn string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| format!("\"{s}\""))
+}
@@ -0,0 +1,567 @@
+use std::borrow::Cow;
+
+use specta::{
+    DataType, EnumRepr, EnumType, EnumVariant, LiteralType, NamedDataType, NamedDataTypeItem,
+    ObjectType, PrimitiveType, TypeDefs,
+};
+
+use crate::{internal::middleware::ProcedureKind, ExportError};
+
+use super::{to_camel_case, to_pascal_case, ExportContext, Exporter};
+
+/// Export Swift bindings. Types are exported as `Codable` structs and enums and a `RspcClient` is generated for calling queries and mutations over HTTP.
+///
+/// The client uses `async`/`await` so requires Swift 5.5 or newer.
+#[derive(Debug, Clone, Default)]
+pub struct Swift {
+    header: Cow<'static, str>,
+}
+
+impl Swift {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string to the top of the generated file. Eg. to disable a linter.
+    pub fn header(self, header: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header: header.into(),
+        }
+    }
+}
+
+impl Exporter for Swift {
+    fn export(&self, ctx: &ExportContext<'_>) -> Result<String, ExportError> {
+        let mut out = String::new();
+        if !self.header.is_empty() {
+            out.push_str(&self.header);
+            out.push('\n');
+        }
+        out.push_str(PRELUDE);
+
+        for ty in ctx.types()? {
+            out.push('\n');
+            out.push_str(&named_datatype(ty, ctx.type_defs()));
+        }
+
+        out.push_str(CLIENT);
+        for procedure in ctx.procedures() {
+            let (prefix, method) = match procedure.kind {
+                ProcedureKind::Query => ("query", "GET"),
+                ProcedureKind::Mutation => ("mutation", "POST"),
+                // Subscriptions require a websocket which the generated client doesn't support.
+                ProcedureKind::Subscription => continue,
+            };
+            let name = format!("{prefix}{}", to_pascal_case(procedure.key));
+            let (arg, input) = match procedure.has_input() {
+                true => (format!("_ input: {}", datatype(procedure.input)), "input"),
+                false => (String::new(), "nil as RspcJSON?"),
+            };
+
+            out.push('\n');
+            if let Some(description) = procedure.meta.description() {
+                for line in description.lines() {
+                    out.push_str(&format!("    /// {line}\n"));
+                }
+            }
+            if procedure.meta.deprecated().is_some() {
+                out.push_str("    @available(*, deprecated)\n");
+            }
+            out.push_str(&format!(
+                "    public func {name}({arg}) async throws -> {} {{\n        try await call(\"{method}\", \"{}\", {input})\n    }}\n",
+                datatype(procedure.result),
+                procedure.key
+            ));
+        }
+        out.push_str("}\n");
+
+        Ok(out)
+    }
+}
+
+const PRELUDE: &str = r#"// This file was generated by [rspc](https://github.com/oscartbeaumont/rspc). Do not edit this file manually.
+import Foundation
+
+public struct RspcCodingKey: CodingKey {
+    public var stringValue: String
+    public var intValue: Int? { nil }
+
+    public init(_ stringValue: String) { self.stringValue = stringValue }
+    public init?(stringValue: String) { self.stringValue = stringValue }
+    public init?(intValue: Int) { return nil }
+}
+
+/// An arbitrary JSON value. Used for types which can't be represented in Swift.
+public enum RspcJSON: Codable, Hashable {
+    case null
+    case bool(Bool)
+    case number(Double)
+    case string(String)
+    case array([RspcJSON])
+    case object([String: RspcJSON])
+
+    public init(from decoder: Decoder) throws {
+        let container = try decoder.singleValueContainer()
+        if container.decodeNil() {
+            self = .null
+        } else if let value = try? container.decode(Bool.self) {
+            self = .bool(value)
+        } else if let value = try? container.decode(Double.self) {
+            self = .number(value)
+        } else if let value = try? container.decode(String.self) {
+            self = .string(value)
+        } else if let value = try? container.decode([RspcJSON].self) {
+            self = .array(value)
+        } else {
+            self = .object(try container.decode([String: RspcJSON].self))
+        }
+    }
+
+    public func encode(to encoder: Encoder) throws {
+        var container = encoder.singleValueContainer()
+        switch self {
+        case .null: try container.encodeNil()
+        case .bool(let value): try container.encode(value)
+        case .number(let value): try container.encode(value)
+        case .string(let value): try container.encode(value)
+        case .array(let value): try container.encode(value)
+        case .object(let value): try container.encode(value)
+        }
+    }
+}
+"#;
+
+const CLIENT: &str = r#"
+public struct RspcError: Error, Decodable {
+    public let code: Int
+    public let message: String
+    public let data: RspcJSON?
+}
+
+private struct RspcResponse<T: Decodable>: Decodable {
+    let result: Result<T, RspcError>
+
+    init(from decoder: Decoder) throws {
+        let container = try decoder.container(keyedBy: RspcCodingKey.self)
+        switch try container.decode(String.self, forKey: RspcCodingKey("type")) {
+        case "value": result = .success(try container.decode(T.self, forKey: RspcCodingKey("value")))
+        default: result = .failure(try container.decode(RspcError.self, forKey: RspcCodingKey("value")))
+        }
+    }
+}
+
+/// Call the queries and mutations of the rspc router over HTTP.
+public final class RspcClient {
+    public let url: URL
+    public let session: URLSession
+
+    public init(url: URL, session: URLSession = .shared) {
+        self.url = url
+        self.session = session
+    }
+
+    private func call<I: Encodable, R: Decodable>(_ method: String, _ key: String, _ input: I?) async throws -> R {
+        var components = URLComponents(url: url.appendingPathComponent(key), resolvingAgainstBaseURL: false)!
+        var body: Data? = nil
+        if let input = input {
+            let data = try JSONEncoder().encode(input)
+            if method == "GET" {
+                components.queryItems = [URLQueryItem(name: "input", value: String(decoding: data, as: UTF8.self))]
+            } else {
+                body = data
+            }
+        }
+
+        var request = URLRequest(url: components.url!)
+        request.httpMethod = method
+        request.httpBody = body
+        request.setValue("application/json", forHTTPHeaderField: "Content-Type")
+
+        let (data, _) = try await session.data(for: request)
+        return try JSONDecoder().decode(RspcResponse<R>.self, from: data).result.get()
+    }
+"#;
+
+fn named_datatype(ty: &NamedDataType, type_defs: &TypeDefs) -> String {
+    let name = ty.name;
+    let mut out = String::new();
+    for comment in ty.comments {
+        out.push_str(&format!("///{comment}\n"));
+    }
+
+    match &ty.item {
+        NamedDataTypeItem::Object(obj) => out.push_str(&structure(name, obj, type_defs, "")),
+        NamedDataTypeItem::Tuple(tuple) => out.push_str(&format!(
+            "public typealias {name} = {}\n",
+            datatype(&DataType::Tuple(tuple.clone()))
+        )),
+        NamedDataTypeItem::Enum(EnumType::Tagged {
+            variants,
+            repr: EnumRepr::External,
+            ..
+        }) if variants.iter().all(|(_, v)| matches!(v, EnumVariant::Unit)) => {
+            out.push_str(&format!("public enum {name}: String, Codable {{\n"));
+            for (variant_name, _) in variants {
+                out.push_str(&format!(
+                    "    case {} = {}\n",
+                    ident(&to_camel_case(variant_name)),
+                    string(variant_name)
+                ));
+            }
+            out.push_str("}\n");
+        }
+        NamedDataTypeItem::Enum(e) => out.push_str(&enumeration(name, e, type_defs)),
+    }
+
+    out
+}
+
+/// The fields of an object as `(key, type)`. Flattened objects are inlined.
+fn fields(obj: &ObjectType, type_defs: &TypeDefs) -> Vec<(&'static str, String)> {
+    let mut fields = Vec::new();
+    for field in &obj.fields {
+        if field.flatten {
+            let inner = match &field.ty {
+                DataType::Object(obj) => Some(obj),
+                DataType::Reference(r) => match type_defs.get(&r.sid) {
+                    Some(Some(NamedDataType {
+                        item: NamedDataTypeItem::Object(obj),
+                        ..
+                    })) => Some(obj),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            if let Some(inner) = inner {
+                fields.extend(self::fields(inner, type_defs));
+            }
+            continue;
+        }
+
+        let ty = datatype(&field.ty);
+        fields.push((
+            field.key,
+            match field.optional && !ty.ends_with('?') {
+                true => format!("{ty}?"),
+                false => ty,
+            },
+        ));
+    }
+    fields
+}
+
+fn structure(name: &str, obj: &ObjectType, type_defs: &TypeDefs, indent: &str) -> String {
+    let fields = fields(obj, type_defs);
+
+    let mut out = format!("{indent}public struct {name}: Codable {{\n");
+    for (key, ty) in &fields {
+        out.push_str(&format!(
+            "{indent}    public var {}: {ty}\n",
+            ident(&property_name(key))
+        ));
+    }
+
+    // `CodingKeys` are only required when a key can't be used as the name of the property.
+    if fields.iter().any(|(key, _)| property_name(key) != *key) {
+        out.push_str(&format!(
+            "\n{indent}    enum CodingKeys: String, CodingKey {{\n"
+        ));
+        for (key, _) in &fields {
+            out.push_str(&format!(
+                "{indent}        case {} = {}\n",
+                ident(&property_name(key)),
+                string(key)
+            ));
+        }
+        out.push_str(&format!("{indent}    }}\n"));
+    }
+
+    out.push_str(&format!("{indent}}}\n"));
+    out
+}
+
+fn enumeration(name: &str, e: &EnumType, type_defs: &TypeDefs) -> String {
+    // The Swift case name, the serialized name and the type of the data of each variant.
+    let variants = match e {
+        EnumType::Untagged { variants, .. } => variants
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("variant{i}"), String::new(), v))
+            .collect::<Vec<_>>(),
+        EnumType::Tagged { variants, .. } => variants
+            .iter()
+            .map(|(n, v)| (to_camel_case(n), n.to_string(), v))
+            .collect(),
+    };
+
+    let mut out = format!("public enum {name}: Codable {{\n");
+    let mut helpers = String::new();
+    let mut payloads = Vec::new();
+    for (case, serialized, v) in &variants {
+        let payload = match v {
+            EnumVariant::Unit => None,
+            EnumVariant::Unnamed(tuple) => Some(datatype(&DataType::Tuple(tuple.clone()))),
+            EnumVariant::Named(obj) => {
+                let helper = match serialized.is_empty() {
+                    true => to_pascal_case(case),
+                    false => to_pascal_case(serialized),
+                };
+                helpers.push('\n');
+                helpers.push_str(&structure(&helper, obj, type_defs, "    "));
+                Some(helper)
+            }
+        };
+
+        match &payload {
+            Some(payload) => out.push_str(&format!("    case {}({payload})\n", ident(case))),
+            None => out.push_str(&format!("    case {}\n", ident(case))),
+        }
+        payloads.push(payload);
+    }
+    out.push_str(&helpers);
+
+    let unknown = format!("throw DecodingError.dataCorrupted(.init(codingPath: decoder.codingPath, debugDescription: \"Unknown variant of {name}\"))");
+    let mut decode = String::new();
+    let mut encode = String::new();
+    // The internal and adjacent representations encode every variant into a keyed container.
+    let mut keyed = false;
+    let variants = variants.iter().zip(&payloads);
+    match e {
+        EnumType::Untagged { .. } => {
+            for ((case, _, _), payload) in variants {
+                let case = ident(case);
+                match payload {
+                    Some(payload) => {
+                        decode.push_str(&format!("        if let value = try? {payload}(from: decoder) {{ self = .{case}(value); return }}\n"));
+                        encode.push_str(&format!(
+                            "        case .{case}(let value): try value.encode(to: encoder)\n"
+                        ));
+                    }
+                    None => {
+                        decode.push_str(&format!("        if (try? decoder.singleValueContainer().decodeNil()) == true {{ self = .{case}; return }}\n"));
+                        encode.push_str(&format!("        case .{case}:\n            var container = encoder.singleValueContainer()\n            try container.encodeNil()\n"));
+                    }
+                }
+            }
+            decode.push_str(&format!("        {unknown}\n"));
+        }
+        EnumType::Tagged {
+            repr: EnumRepr::External,
+            ..
+        } => {
+            // Unit variants are serialized as a string
+            if payloads.iter().any(Option::is_none) {
+                decode.push_str("        if let tag = try? decoder.singleValueContainer().decode(String.self) {\n            switch tag {\n");
+                for ((case, serialized, _), payload) in variants.clone() {
+                    if payload.is_none() {
+                        decode.push_str(&format!(
+                            "            case {}: self = .{}; return\n",
+                            string(serialized),
+                            ident(case)
+                        ));
+                    }
+                }
+                decode.push_str("            default: break\n            }\n        }\n");
+            }
+            decode.push_str(
+                "        let container = try decoder.container(keyedBy: RspcCodingKey.self)\n",
+            );
+
+            for ((case, serialized, _), payload) in variants {
+                let (case, key) = (ident(case), string(serialized));
+                match payload {
+                    Some(payload) => {
+                        decode.push_str(&format!("        if let value = try container.decodeIfPresent({payload}.self, forKey: RspcCodingKey({key})) {{ self = .{case}(value); return }}\n"));
+                        encode.push_str(&format!("        case .{case}(let value):\n            var container = encoder.container(keyedBy: RspcCodingKey.self)\n            try container.encode(value, forKey: RspcCodingKey({key}))\n"));
+                    }
+                    None => encode.push_str(&format!("        case .{case}:\n            var container = encoder.singleValueContainer()\n            try container.encode({key})\n")),
+                }
+            }
+            decode.push_str(&format!("        {unknown}\n"));
+        }
+        EnumType::Tagged {
+            repr: EnumRepr::Internal { tag },
+            ..
+        } => {
+            let tag = string(tag);
+            decode.push_str(&format!("        let container = try decoder.container(keyedBy: RspcCodingKey.self)\n        switch try container.decode(String.self, forKey: RspcCodingKey({tag})) {{\n"));
+            keyed = true;
+            for ((case, serialized, _), payload) in variants {
+                let (case, key) = (ident(case), string(serialized));
+                match payload {
+                    Some(payload) => {
+                        decode.push_str(&format!(
+                            "        case {key}: self = .{case}(try {payload}(from: decoder))\n"
+                        ));
+                        encode.push_str(&format!("        case .{case}(let value):\n            try container.encode({key}, forKey: RspcCodingKey({tag}))\n            try value.encode(to: encoder)\n"));
+                    }
+                    None => {
+                        decode.push_str(&format!("        case {key}: self = .{case}\n"));
+                        encode.push_str(&format!("        case .{case}:\n            try container.encode({key}, forKey: RspcCodingKey({tag}))\n"));
+                    }
+                }
+            }
+            decode.push_str(&format!("        default: {unknown}\n        }}\n"));
+        }
+        EnumType::Tagged {
+            repr: EnumRepr::Adjacent { tag, content },
+            ..
+        } => {
+            let (tag, content) = (string(tag), string(content));
+            decode.push_str(&format!("        let container = try decoder.container(keyedBy: RspcCodingKey.self)\n        switch try container.decode(String.self, forKey: RspcCodingKey({tag})) {{\n"));
+            keyed = true;
+            for ((case, serialized, _), payload) in variants {
+                let (case, key) = (ident(case), string(serialized));
+                match payload {
+                    Some(payload) => {
+                        decode.push_str(&format!("        case {key}: self = .{case}(try container.decode({payload}.self, forKey: RspcCodingKey({content})))\n"));
+                        encode.push_str(&format!("        case .{case}(let value):\n            try container.encode({key}, forKey: RspcCodingKey({tag}))\n            try container.encode(value, forKey: RspcCodingKey({content}))\n"));
+                    }
+                    None => {
+                        decode.push_str(&format!("        case {key}: self = .{case}\n"));
+                        encode.push_str(&format!("        case .{case}:\n            try container.encode({key}, forKey: RspcCodingKey({tag}))\n"));
+                    }
+                }
+            }
+            decode.push_str(&format!("        default: {unknown}\n        }}\n"));
+        }
+    }
+
+    let container = match keyed {
+        true => "        var container = encoder.container(keyedBy: RspcCodingKey.self)\n",
+        false => "",
+    };
+
+    out.push_str(&format!(
+        "\n    public init(from decoder: Decoder) throws {{\n{decode}    }}\n\n    public func encode(to encoder: Encoder) throws {{\n{container}        switch self {{\n{encode}        }}\n    }}\n}}\n"
+    ));
+    out
+}
+
+/// Convert a [`DataType`] into a Swift type. Types which can't be represented are exported as `RspcJSON`.
+fn datatype(ty: &DataType) -> String {
+    match ty {
+        DataType::Any | DataType::Generic(_) | DataType::Object(_) | DataType::Enum(_) => {
+            "RspcJSON".into()
+        }
+        DataType::Primitive(ty) => match ty {
+            PrimitiveType::i8 => "Int8",
+            PrimitiveType::i16 => "Int16",
+            PrimitiveType::i32 => "Int32",
+            PrimitiveType::i64 => "Int64",
+            PrimitiveType::isize => "Int",
+            PrimitiveType::u8 => "UInt8",
+            PrimitiveType::u16 => "UInt16",
+            PrimitiveType::u32 => "UInt32",
+            PrimitiveType::u64 => "UInt64",
+            PrimitiveType::usize => "UInt",
+            PrimitiveType::i128 | PrimitiveType::u128 => "Decimal",
+            PrimitiveType::f32 => "Float",
+            PrimitiveType::f64 => "Double",
+            PrimitiveType::bool => "Bool",
+            PrimitiveType::char | PrimitiveType::String => "String",
+        }
+        .into(),
+        DataType::Literal(ty) => match ty {
+            LiteralType::f32(_) | LiteralType::f64(_) => "Double",
+            LiteralType::bool(_) => "Bool",
+            LiteralType::String(_) => "String",
+            LiteralType::None => "RspcJSON",
+            _ => "Int",
+        }
+        .into(),
+        DataType::List(ty) => format!("[{}]", datatype(ty)),
+        DataType::Nullable(ty) => match datatype(ty) {
+            ty if ty.ends_with('?') => ty,
+            ty => format!("{ty}?"),
+        },
+        DataType::Record(ty) => format!("[String: {}]", datatype(&ty.1)),
+        DataType::Named(ty) => ty.name.into(),
+        DataType::Tuple(ty) => match &ty.fields[..] {
+            [] => "RspcJSON".into(),
+            [ty] => datatype(ty),
+            _ => "[RspcJSON]".into(),
+        },
+        DataType::Reference(ty) => ty.name.into(),
+    }
+}
+
+/// The name of the property for a key. The key is used as is if it's a valid identifier.
+fn property_name(key: &str) -> String {
+    let valid = key.chars().all(|c| c.is_alphanumeric() || c == '_')
+        && !key.starts_with(|c: char| c.is_numeric());
+
+    match valid && !key.is_empty() {
+        true => key.to_string(),
+        false => to_camel_case(key),
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "Any",
+    "Self",
+    "as",
+    "associatedtype",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "continue",
+    "default",
+    "defer",
+    "deinit",
+    "do",
+    "else",
+    "enum",
+    "extension",
+    "fallthrough",
+    "false",
+    "fileprivate",
+    "for",
+    "func",
+    "guard",
+    "if",
+    "import",
+    "in",
+    "init",
+    "inout",
+    "internal",
+    "is",
+    "let",
+    "nil",
+    "open",
+    "operator",
+    "private",
+    "protocol",
+    "public",
+    "repeat",
+    "rethrows",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "subscript",
+    "super",
+    "switch",
+    "throw",
+    "throws",
+    "true",
+    "try",
+    "typealias",
+    "var",
+    "where",
+    "while",
+];
+
+/// Escape keywords so they can be used as identifiers.
+fn ident(name: &str) -> String {
+    match KEYWORDS.contains(&name) {
+        true => format!("`{name}`"),
+        false => name.to_string(),
+    }
+}
+
+fn string(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_else(|_| format!("\"{s}\""))
+}
@@ -0,0 +1,283 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use specta::ts::{self, datatype, ExportConfiguration};
+
+use crate::{internal::middleware::ProcedureKind, ExportError, ProcedureMeta};
+
+use super::{
+    to_camel_case, walk_item_references, walk_references, BigIntExport, ExportContext, Exporter,
+};
+
+const GENERATED: &str = "// This file was generated by [rspc](https://github.com/oscartbeaumont/rspc). Do not edit this file manually.\n";
+
+/// Export Typescript bindings for use with the rspc frontend libraries.
+///
+/// This is what [`BuiltRouter::export_ts`](crate::BuiltRouter::export_ts) uses under the hood.
+#[derive(Debug, Clone, Default)]
+pub struct Typescript {
+    header: Cow<'static, str>,
+    bigint: BigIntExport,
+    path_constants: bool,
+}
+
+impl Typescript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a string to the top of the generated file. Eg. to disable a linter.
+    pub fn header(self, header: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header: header.into(),
+            ..self
+        }
+    }
+
+    /// Configure how `i64`, `u64`, `i128` and `u128` are exported. By default exporting them is an error.
+    pub fn bigint(self, bigint: BigIntExport) -> Self {
+        Self { bigint, ..self }
+    }
+
+    /// Export a `const` object for each kind of procedure mapping the camel cased key to the key. Eg. `queries.usersGet === "users.get"`.
+    pub fn path_constants(self, path_constants: bool) -> Self {
+        Self {
+            path_constants,
+            ..self
+        }
+    }
+
+    fn config(&self) -> ExportConfiguration {
+        ExportConfiguration::new().bigint(match self.bigint {
+            BigIntExport::Fail => ts::BigIntExportBehavior::FailWithReason(
+                "rspc does not support exporting bigint types (i64, u64, i128, u128) by default because they are lossily decoded by `JSON.parse` on the frontend. Configure `ExportConfig::bigint` to export them. Tracking issue: https://github.com/oscartbeaumont/rspc/issues/93",
+            ),
+            BigIntExport::String => ts::BigIntExportBehavior::String,
+            BigIntExport::Number => ts::BigIntExportBehavior::Number,
+            BigIntExport::BigInt => ts::BigIntExportBehavior::BigInt,
+        })
+    }
+
+    /// The header, `Procedures` type and path constants without any of the named types.
+    fn export_procedures(
+        &self,
+        ctx: &ExportContext<'_>,
+        imports: &str,
+    ) -> Result<String, ExportError> {
+        let config = self.config();
+
+        let mut out = String::new();
+        if !self.header.is_empty() {
+            out.push_str(&self.header);
+            out.push('\n');
+        }
+        out.push_str(GENERATED);
+        out.push_str(imports);
+
+        let queries_ts = generate_procedures_ts(&config, ctx, ProcedureKind::Query)?;
+        let mutations_ts = generate_procedures_ts(&config, ctx, ProcedureKind::Mutation)?;
+        let subscriptions_ts = generate_procedures_ts(&config, ctx, ProcedureKind::Subscription)?;
+
+        out.push_str(&format!(
+            r#"
+export type Procedures = {{
+    queries: {queries_ts},
+    mutations: {mutations_ts},
+    subscriptions: {subscriptions_ts}
+}};
+"#
+        ));
+
+        if self.path_constants {
+            for (name, kind) in [
+                ("queries", ProcedureKind::Query),
+                ("mutations", ProcedureKind::Mutation),
+                ("subscriptions", ProcedureKind::Subscription),
+            ] {
+                // Camel casing isn't reversible so two keys could end up with the same constant, eg. `a.b` and `aB`.
+                let mut constants = BTreeMap::new();
+                for procedure in ctx.procedures_of(kind) {
+                    if let Some(existing) =
+                        constants.insert(to_camel_case(procedure.key), procedure.key)
+                    {
+                        return Err(ExportError::DuplicatePathConstant(
+                            to_camel_case(procedure.key),
+                            existing.to_string(),
+                            procedure.key.to_string(),
+                        ));
+                    }
+                }
+
+                out.push_str(&format!("\nexport const {name} = {{\n"));
+                for procedure in ctx.procedures_of(kind) {
+                    out.push_str(&format!(
+                        "    {}: \"{}\",\n",
+                        to_camel_case(procedure.key),
+                        procedure.key
+                    ));
+                }
+                out.push_str("} as const;\n");
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Export the procedures into `export_path` and every named type into its own module in `types_dir`.
+    ///
+    /// `types_dir` is relative to the directory of `export_path`. Returns the paths of every file which was written.
+    pub(crate) fn export_split(
+        &self,
+        ctx: &ExportContext<'_>,
+        export_path: &Path,
+        types_dir: &Path,
+    ) -> Result<Vec<PathBuf>, ExportError> {
+        let config = self.config();
+        let export_dir = export_path.parent().unwrap_or_else(|| Path::new(""));
+        let types_path = export_dir.join(types_dir);
+        fs::create_dir_all(&types_path)?;
+
+        let mut files = Vec::new();
+        let mut index = GENERATED.to_string();
+        for ty in ctx.types()? {
+            let mut refs = BTreeSet::new();
+            walk_item_references(&ty.item, &mut |r| {
+                refs.insert(r.name);
+            });
+            refs.remove(ty.name);
+
+            let mut out = GENERATED.to_string();
+            for r in &refs {
+                out.push_str(&format!("import type {{ {r} }} from \"./{r}\";\n"));
+            }
+            out.push_str(&format!(
+                "\n{}\n",
+                ts::export_datatype(&config, ty, ctx.type_defs())?
+            ));
+
+            let path = types_path.join(format!("{}.ts", ty.name));
+            fs::write(&path, out)?;
+            files.push(path);
+            index.push_str(&format!("export * from \"./{}\";\n", ty.name));
+        }
+
+        let index_path = types_path.join("index.ts");
+        fs::write(&index_path, index)?;
+        files.push(index_path);
+
+        // The `Procedures` type references the types used by the procedures so they must be imported.
+        let mut refs = BTreeSet::new();
+        for procedure in ctx.procedures() {
+            for ty in [procedure.input, procedure.result] {
+                walk_references(ty, &mut |r| {
+                    refs.insert(r.name);
+                });
+            }
+        }
+
+        let import_path = types_dir
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(c) => Some(c.to_string_lossy().to_string()),
+                Component::ParentDir => Some("..".into()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut imports = String::new();
+        if !refs.is_empty() {
+            imports.push_str(&format!(
+                "import type {{ {} }} from \"./{import_path}\";\n",
+                refs.into_iter().collect::<Vec<_>>().join(", ")
+            ));
+        }
+        imports.push_str(&format!("\nexport * from \"./{import_path}\";\n"));
+
+        fs::write(export_path, self.export_procedures(ctx, &imports)?)?;
+        files.insert(0, export_path.to_path_buf());
+
+        Ok(files)
+    }
+}
+
+impl Exporter for Typescript {
+    fn export(&self, ctx: &ExportContext<'_>) -> Result<String, ExportError> {
+        let config = self.config();
+        let mut out = self.export_procedures(ctx, "")?;
+
+        for ty in ctx.types()? {
+            out.push_str(&format!(
+                "\n{}\n",
+                ts::export_datatype(&config, ty, ctx.type_defs())?
+            ));
+        }
+
+        Ok(out)
+    }
+}
+
+// TODO: Move this out into a Specta API
+fn generate_procedures_ts(
+    config: &ExportConfiguration,
+    ctx: &ExportContext<'_>,
+    kind: ProcedureKind,
+) -> Result<String, ExportError> {
+    let procedures = ctx.procedures_of(kind).collect::<Vec<_>>();
+    if procedures.is_empty() {
+        return Ok("never".to_string());
+    }
+
+    Ok(procedures
+        .into_iter()
+        .map(|procedure| -> Result<String, ExportError> {
+            let key = procedure.key;
+            let input = match procedure.has_input() {
+                true => datatype(config, procedure.input, ctx.type_defs())?,
+                false => "never".into(),
+            };
+            let result_ts = datatype(config, procedure.result, ctx.type_defs())?;
+
+            let docs = generate_jsdoc(procedure.meta);
+
+            // TODO: Specta API
+            Ok(format!(
+                r#"
+        {docs}{{ key: "{key}", input: {input}, result: {result_ts} }}"#
+            ))
+        })
+        .collect::<Result<Vec<_>, ExportError>>()?
+        .join(" | "))
+}
+
+fn generate_jsdoc(meta: &ProcedureMeta) -> String {
+    if meta.is_empty() {
+        return String::new();
+    }
+
+    let mut lines = Vec::new();
+    if let Some(description) = meta.description() {
+        lines.extend(description.lines().map(ToString::to_string));
+    }
+    for tag in meta.tags() {
+        lines.push(format!("@tag {tag}"));
+    }
+    for (key, value) in meta.extra() {
+        lines.push(format!("@meta {key} {value}"));
+    }
+    if let Some(reason) = meta.deprecated() {
+        lines.push(format!("@deprecated {reason}"));
+    }
+
+    let mut docs = "/**\n".to_string();
+    for line in lines {
+        // A `*/` would end the comment early
+        docs.push_str(&format!("         * {}\n", line.replace("*/", "*\\/")));
+    }
+    docs.push_str("         */\n        ");
+    docs
+}
@@ -0,0 +1,93 @@
+use crate::{OpenApiConfig, Recorder};
+
+use super::{CorsConfig, CsrfConfig};
+
+/// EndpointConfig is used to configure the httpz endpoint created by [`BuiltRouter::endpoint_with_config`](crate::BuiltRouter::endpoint_with_config).
+#[derive(Debug, Clone, Default)]
+pub struct EndpointConfig {
+    pub(crate) csrf: Option<CsrfConfig>,
+    pub(crate) cors: Option<CorsConfig>,
+    pub(crate) max_body_size: Option<usize>,
+    pub(crate) max_batch_len: Option<usize>,
+    pub(crate) max_websocket_message_size: Option<usize>,
+    pub(crate) openapi: Option<OpenApiConfig>,
+    pub(crate) manifest: bool,
+    pub(crate) recorder: Option<Recorder>,
+}
+
+impl EndpointConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable CSRF protection for mutations and batched requests.
+    pub fn csrf(self, csrf: CsrfConfig) -> Self {
+        Self {
+            csrf: Some(csrf),
+            ..self
+        }
+    }
+
+    /// Enable CORS handling including responding to preflight `OPTIONS` requests.
+    ///
+    /// This is handled by rspc so it works the same on every webserver supported by httpz.
+    pub fn cors(self, cors: CorsConfig) -> Self {
+        Self {
+            cors: Some(cors),
+            ..self
+        }
+    }
+
+    /// The maximum size in bytes of a HTTP request body. Larger requests will be rejected with a `413 Payload Too Large` response.
+    ///
+    /// Requests with a larger `Content-Length` are rejected before rspc does anything else with them.
+    /// The webserver reads the body before it's handed to rspc so chunked requests should also be limited by your webserver, eg. with `tower_http::limit::RequestBodyLimitLayer` for Axum.
+    pub fn max_body_size(self, max_body_size: usize) -> Self {
+        Self {
+            max_body_size: Some(max_body_size),
+            ..self
+        }
+    }
+
+    /// The maximum number of requests in a single `_batch` request.
+    ///
+    /// The batch stops being parsed as soon as it exceeds this so it's rejected without deserializing the remaining requests.
+    pub fn max_batch_len(self, max_batch_len: usize) -> Self {
+        Self {
+            max_batch_len: Some(max_batch_len),
+            ..self
+        }
+    }
+
+    /// The maximum size in bytes of a single websocket message.
+    ///
+    /// The message can't be correlated to a request so the connection will be closed with a `1009 Message Too Big` status if it is exceeded.
+    /// httpz doesn't expose the websocket library's own limits so the message has already been read into memory by then.
+    pub fn max_websocket_message_size(self, max_websocket_message_size: usize) -> Self {
+        Self {
+            max_websocket_message_size: Some(max_websocket_message_size),
+            ..self
+        }
+    }
+
+    /// Serve the OpenAPI document for the router at `rspc.openapi.json` and a page to explore it at `rspc.docs`.
+    pub fn openapi(self, openapi: OpenApiConfig) -> Self {
+        Self {
+            openapi: Some(openapi),
+            ..self
+        }
+    }
+
+    /// Serve the [`Manifest`](crate::Manifest) of the router at `rspc.manifest`.
+    pub fn manifest(self, manifest: bool) -> Self {
+        Self { manifest, ..self }
+    }
+
+    /// Record every request and response handled by the endpoint, including those sent over the websocket.
+    pub fn recorder(self, recorder: Recorder) -> Self {
+        Self {
+            recorder: Some(recorder),
+            ..self
+        }
+    }
+}
@@ -0,0 +1,258 @@
+use std::{borrow::Cow, time::Duration};
+
+use httpz::http::{header, HeaderMap, HeaderValue, Response, StatusCode};
+
+/// Cross-origin resource sharing (CORS) configuration for the httpz endpoint.
+///
+/// This allows your rspc API to be hosted on a different origin to your frontend without requiring framework specific middleware.
+///
+/// ```rust
+/// use std::time::Duration;
+/// use rspc::integrations::httpz::CorsConfig;
+///
+/// let cors = CorsConfig::new()
+///     .allow_origin("https://example.com")
+///     .allow_credentials(true)
+///     .allow_header("X-CSRF-Token")
+///     .max_age(Duration::from_secs(600));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CorsConfig {
+    allow_any_origin: bool,
+    allowed_origins: Vec<Cow<'static, str>>,
+    allow_credentials: bool,
+    allowed_headers: Vec<Cow<'static, str>>,
+    max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow requests from `origin`. The origin must be in the form `scheme://host[:port]` without a trailing slash.
+    pub fn allow_origin(mut self, origin: impl Into<Cow<'static, str>>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// Allow requests from any origin.
+    ///
+    /// # Panics
+    ///
+    /// If credentials are allowed. This would let any website make authenticated requests to your API so the allowed origins must be listed with [`CorsConfig::allow_origin`] instead.
+    pub fn allow_any_origin(self) -> Self {
+        assert!(
+            !self.allow_credentials,
+            "rspc: CORS can't allow any origin when credentials are allowed"
+        );
+
+        Self {
+            allow_any_origin: true,
+            ..self
+        }
+    }
+
+    /// Allow the browser to send cookies and other credentials with cross-origin requests.
+    ///
+    /// # Panics
+    ///
+    /// If any origin is allowed. Refer to [`CorsConfig::allow_any_origin`].
+    pub fn allow_credentials(self, allow_credentials: bool) -> Self {
+        assert!(
+            !(allow_credentials && self.allow_any_origin),
+            "rspc: CORS can't allow credentials when any origin is allowed"
+        );
+
+        Self {
+            allow_credentials,
+            ..self
+        }
+    }
+
+    /// Allow the frontend to send a header in addition to `Content-Type`.
+    pub fn allow_header(mut self, header: impl Into<Cow<'static, str>>) -> Self {
+        self.allowed_headers.push(header.into());
+        self
+    }
+
+    /// How long the browser is allowed to cache the result of a preflight request.
+    pub fn max_age(self, max_age: Duration) -> Self {
+        Self {
+            max_age: Some(max_age),
+            ..self
+        }
+    }
+
+    fn is_allowed(&self, origin: &HeaderValue) -> bool {
+        self.allow_any_origin
+            || origin
+                .to_str()
+                .map(|origin| self.allowed_origins.iter().any(|o| o == origin))
+                .unwrap_or(false)
+    }
+
+    /// Build the response to a preflight `OPTIONS` request.
+    pub(crate) fn preflight(
+        &self,
+        origin: Option<&HeaderValue>,
+    ) -> Result<Response<Vec<u8>>, httpz::Error> {
+        let mut resp = Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .body(vec![])?;
+
+        if self.apply(origin, resp.headers_mut()) {
+            let headers = resp.headers_mut();
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_METHODS,
+                HeaderValue::from_static("GET, POST, OPTIONS"),
+            );
+
+            let allowed_headers = std::iter::once("Content-Type")
+                .chain(self.allowed_headers.iter().map(|h| h.as_ref()))
+                .collect::<Vec<_>>()
+                .join(", ");
+            if let Ok(v) = HeaderValue::from_str(&allowed_headers) {
+                headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, v);
+            }
+
+            if let Some(max_age) = self.max_age {
+                headers.insert(header::ACCESS_CONTROL_MAX_AGE, max_age.as_secs().into());
+            }
+        }
+
+        Ok(resp)
+    }
+
+    /// Add the CORS headers to a response. Returns `false` if the origin is not allowed.
+    pub(crate) fn apply(&self, origin: Option<&HeaderValue>, headers: &mut HeaderMap) -> bool {
+        // The response depends on the `Origin` header so caches must not share it between origins.
+        headers.append(header::VARY, HeaderValue::from_static("Origin"));
+
+        let Some(origin) = origin.filter(|o| self.is_allowed(o)) else {
+            return false;
+        };
+
+        // The builder ensures credentials are never allowed with a wildcard
+        if self.allow_any_origin {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_ORIGIN,
+                HeaderValue::from_static("*"),
+            );
+        } else {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin.clone());
+        }
+
+        if self.allow_credentials {
+            headers.insert(
+                header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origin(origin: &'static str) -> Option<HeaderValue> {
+        Some(HeaderValue::from_static(origin))
+    }
+
+    fn config() -> CorsConfig {
+        CorsConfig::new()
+            .allow_origin("https://example.com")
+            .allow_header("X-CSRF-Token")
+            .max_age(Duration::from_secs(600))
+    }
+
+    #[test]
+    fn test_preflight() {
+        let resp = config()
+            .preflight(origin("https://example.com").as_ref())
+            .unwrap();
+        let headers = resp.headers();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_METHODS],
+            "GET, POST, OPTIONS"
+        );
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_HEADERS],
+            "Content-Type, X-CSRF-Token"
+        );
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+        assert_eq!(headers[header::VARY], "Origin");
+
+        let resp = config()
+            .preflight(origin("https://evil.com").as_ref())
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+        assert!(!resp
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+        assert!(!resp
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_ALLOW_METHODS));
+    }
+
+    #[test]
+    fn test_allowed_origin() {
+        let mut headers = HeaderMap::new();
+        assert!(config().apply(origin("https://example.com").as_ref(), &mut headers));
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+        assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS));
+
+        let mut headers = HeaderMap::new();
+        assert!(CorsConfig::new()
+            .allow_any_origin()
+            .apply(origin("https://example.com").as_ref(), &mut headers));
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+    }
+
+    #[test]
+    fn test_denied_origin() {
+        for origin in [origin("https://evil.com"), origin("null"), None] {
+            let mut headers = HeaderMap::new();
+            assert!(!config().apply(origin.as_ref(), &mut headers));
+            assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_ORIGIN));
+            assert!(!headers.contains_key(header::ACCESS_CONTROL_ALLOW_CREDENTIALS));
+            assert_eq!(headers[header::VARY], "Origin");
+        }
+    }
+
+    #[test]
+    fn test_credentials() {
+        let mut headers = HeaderMap::new();
+        assert!(config()
+            .allow_credentials(true)
+            .apply(origin("https://example.com").as_ref(), &mut headers));
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "https://example.com"
+        );
+        assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_credentials_with_any_origin() {
+        CorsConfig::new().allow_any_origin().allow_credentials(true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_any_origin_with_credentials() {
+        CorsConfig::new().allow_credentials(true).allow_any_origin();
+    }
+}
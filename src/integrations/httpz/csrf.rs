@@ -0,0 +1,277 @@
+use std::borrow::Cow;
+
+use httpz::http::{header, HeaderMap};
+
+use crate::ExecError;
+
+/// Cross-site request forgery (CSRF) protection for the httpz endpoint.
+///
+/// When enabled all mutations and `_batch` requests are checked prior to the context function being run.
+/// Queries sent as `GET` requests are not checked as they should never cause side effects.
+/// Browsers don't allow setting custom headers on websocket upgrades so with double submit enabled the token must be sent in a query parameter named after the cookie instead, eg. `/rspc/ws?csrf_token=...`.
+///
+/// ```rust
+/// use rspc::integrations::httpz::CsrfConfig;
+///
+/// let csrf = CsrfConfig::new()
+///     .allow_origin("https://example.com")
+///     .double_submit("csrf_token", "X-CSRF-Token");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CsrfConfig {
+    allowed_origins: Vec<Cow<'static, str>>,
+    double_submit: Option<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl CsrfConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allow requests where the `Origin` header (or the origin of the `Referer` header if `Origin` is missing) matches `origin`.
+    ///
+    /// The origin must be in the form `scheme://host[:port]` without a trailing slash.
+    /// If no origins are allowed, the `Origin` and `Referer` headers will not be checked.
+    pub fn allow_origin(mut self, origin: impl Into<Cow<'static, str>>) -> Self {
+        self.allowed_origins.push(origin.into());
+        self
+    }
+
+    /// Require the value of the header `header_name` to match the value of the cookie `cookie_name`.
+    ///
+    /// It is up to your application to set the cookie to an unguessable value and for your frontend to copy it into the header.
+    pub fn double_submit(
+        self,
+        cookie_name: impl Into<Cow<'static, str>>,
+        header_name: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Self {
+            double_submit: Some((cookie_name.into(), header_name.into())),
+            ..self
+        }
+    }
+
+    /// Check the origin of a request.
+    pub(crate) fn check_origin(&self, headers: &HeaderMap) -> Result<(), ExecError> {
+        if self.allowed_origins.is_empty() {
+            return Ok(());
+        }
+
+        let origin = request_origin(headers).ok_or(ExecError::ErrCsrfCheckFailed)?;
+        if !self.allowed_origins.iter().any(|o| o == origin) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("Rejecting request from disallowed origin '{}'", origin);
+
+            return Err(ExecError::ErrCsrfCheckFailed);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn check(&self, headers: &HeaderMap) -> Result<(), ExecError> {
+        self.check_origin(headers)?;
+
+        if let Some((cookie_name, header_name)) = &self.double_submit {
+            let header = headers
+                .get(header_name.as_ref())
+                .and_then(|v| v.to_str().ok());
+            check_token(headers, cookie_name, header)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check a websocket upgrade. The double submit token is taken from the query parameter named after the cookie as browsers won't let us set custom headers on them.
+    pub(crate) fn check_websocket(
+        &self,
+        headers: &HeaderMap,
+        query: Option<&str>,
+    ) -> Result<(), ExecError> {
+        self.check_origin(headers)?;
+
+        if let Some((cookie_name, _)) = &self.double_submit {
+            let token = query
+                .into_iter()
+                .flat_map(|q| q.split('&'))
+                .filter_map(|p| p.split_once('='))
+                .find(|(n, _)| n == cookie_name)
+                .map(|(_, v)| v);
+            check_token(headers, cookie_name, token)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check the double submit `token` sent with the request matches the value of the cookie `cookie_name`.
+fn check_token(
+    headers: &HeaderMap,
+    cookie_name: &str,
+    token: Option<&str>,
+) -> Result<(), ExecError> {
+    let cookie = request_cookie(headers, cookie_name)
+        .filter(|v| !v.is_empty())
+        .ok_or(ExecError::ErrCsrfCheckFailed)?;
+    let token = token.ok_or(ExecError::ErrCsrfCheckFailed)?;
+
+    match constant_time_eq(cookie.as_bytes(), token.as_bytes()) {
+        true => Ok(()),
+        false => Err(ExecError::ErrCsrfCheckFailed),
+    }
+}
+
+/// Get the origin of the request from the `Origin` header falling back to the `Referer` header.
+fn request_origin(headers: &HeaderMap) -> Option<&str> {
+    if let Some(origin) = headers.get(header::ORIGIN).and_then(|v| v.to_str().ok()) {
+        // Some browsers send `null` for privacy sensitive contexts. It can never match an allowed origin.
+        return (origin != "null").then_some(origin);
+    }
+
+    let referer = headers.get(header::REFERER)?.to_str().ok()?;
+    let (scheme, rest) = referer.split_once("://")?;
+    let host_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    Some(&referer[..scheme.len() + 3 + host_len])
+}
+
+/// Get the value of the cookie `name` from the `Cookie` headers of the request.
+fn request_cookie<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| v.trim_matches('"'))
+}
+
+// We don't want to leak how much of the token matched through timing.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use httpz::http::HeaderValue;
+
+    use super::*;
+
+    fn headers(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+        headers
+            .iter()
+            .map(|(k, v)| (k.parse().unwrap(), HeaderValue::from_static(v)))
+            .collect()
+    }
+
+    fn origin_config() -> CsrfConfig {
+        CsrfConfig::new().allow_origin("https://example.com")
+    }
+
+    #[test]
+    fn test_allowed_origin() {
+        let csrf = origin_config();
+        assert!(csrf
+            .check(&headers(&[("origin", "https://example.com")]))
+            .is_ok());
+        assert!(csrf
+            .check(&headers(&[("referer", "https://example.com/page?a=b")]))
+            .is_ok());
+        assert!(CsrfConfig::new().check(&headers(&[])).is_ok());
+    }
+
+    #[test]
+    fn test_missing_origin() {
+        let csrf = origin_config();
+        assert!(matches!(
+            csrf.check(&headers(&[])),
+            Err(ExecError::ErrCsrfCheckFailed)
+        ));
+        assert!(matches!(
+            csrf.check_origin(&headers(&[("origin", "null")])),
+            Err(ExecError::ErrCsrfCheckFailed)
+        ));
+    }
+
+    #[test]
+    fn test_mismatched_origin() {
+        let csrf = origin_config();
+        for headers in [
+            headers(&[("origin", "https://evil.com")]),
+            headers(&[("origin", "https://example.com.evil.com")]),
+            headers(&[("referer", "https://evil.com/https://example.com")]),
+            // `Origin` takes precedence over `Referer`
+            headers(&[
+                ("origin", "https://evil.com"),
+                ("referer", "https://example.com/"),
+            ]),
+        ] {
+            assert!(matches!(
+                csrf.check(&headers),
+                Err(ExecError::ErrCsrfCheckFailed)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_double_submit() {
+        let csrf = CsrfConfig::new().double_submit("csrf_token", "X-CSRF-Token");
+        assert!(csrf
+            .check(&headers(&[
+                ("cookie", "session=abc; csrf_token=token"),
+                ("x-csrf-token", "token"),
+            ]))
+            .is_ok());
+
+        for headers in [
+            headers(&[("cookie", "csrf_token=token")]),
+            headers(&[("x-csrf-token", "token")]),
+            headers(&[("cookie", "csrf_token="), ("x-csrf-token", "")]),
+            headers(&[("cookie", "csrf_token=token"), ("x-csrf-token", "other")]),
+        ] {
+            assert!(matches!(
+                csrf.check(&headers),
+                Err(ExecError::ErrCsrfCheckFailed)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_websocket() {
+        let csrf = origin_config();
+        assert!(csrf
+            .check_websocket(&headers(&[("origin", "https://example.com")]), None)
+            .is_ok());
+        assert!(csrf.check_websocket(&headers(&[]), None).is_err());
+        assert!(csrf
+            .check_websocket(&headers(&[("origin", "https://evil.com")]), None)
+            .is_err());
+
+        // Double submit only must not let a cross-site page open a websocket without the token
+        let csrf = CsrfConfig::new().double_submit("csrf_token", "X-CSRF-Token");
+        let cookie = headers(&[
+            ("origin", "https://evil.com"),
+            ("cookie", "csrf_token=token"),
+        ]);
+        assert!(csrf
+            .check_websocket(&cookie, Some("a=b&csrf_token=token"))
+            .is_ok());
+        for query in [
+            None,
+            Some(""),
+            Some("csrf_token="),
+            Some("csrf_token=other"),
+        ] {
+            assert!(matches!(
+                csrf.check_websocket(&cookie, query),
+                Err(ExecError::ErrCsrfCheckFailed)
+            ));
+        }
+        assert!(csrf
+            .check_websocket(&headers(&[]), Some("csrf_token=token"))
+            .is_err());
+    }
+}
@@ -1,21 +1,27 @@
 use futures::{stream::FuturesUnordered, StreamExt};
 use httpz::{
-    http::{Method, Response, StatusCode},
+    http::{header, HeaderMap, Method, Response, StatusCode},
     Endpoint, GenericEndpoint, HttpEndpoint, HttpResponse,
 };
 
+use serde::de::{self, DeserializeSeed, Deserializer, SeqAccess, Visitor};
 use serde_json::Value;
 use std::{
     borrow::Cow,
+    cell::Cell,
+    fmt,
     sync::{Arc, Mutex},
 };
 
 use crate::{
-    internal::exec::{self, Executor, ExecutorResult, NoOpSubscriptionManager},
-    BuiltRouter,
+    internal::exec::{self, AsyncRuntime, Executor, ExecutorResult, NoOpSubscriptionManager},
+    BuiltRouter, ExecError, SwappableRouter, VersionReq,
 };
 
-use super::{handle_websocket, CookieJar, TCtxFunc};
+#[cfg(feature = "tokio")]
+use crate::internal::exec::TokioRuntime;
+
+use super::{handle_websocket, CookieJar, EndpointConfig, TCtxFunc};
 
 // TODO: Make this whole file runtime agnostic once httpz is
 // TODO: Remove all panics lol
@@ -25,41 +31,115 @@ impl<TCtx> BuiltRouter<TCtx>
 where
     TCtx: Clone + Send + Sync + 'static,
 {
+    #[cfg(feature = "tokio")]
     pub fn endpoint<TCtxFnMarker: Send + Sync + 'static, TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>>(
         self: Arc<Self>,
         ctx_fn: TCtxFn,
     ) -> Endpoint<impl HttpEndpoint> {
-        let executor = Executor::new(self);
+        self.endpoint_with_config(EndpointConfig::new(), ctx_fn)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn endpoint_with_config<
+        TCtxFnMarker: Send + Sync + 'static,
+        TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
+    >(
+        self: Arc<Self>,
+        config: EndpointConfig,
+        ctx_fn: TCtxFn,
+    ) -> Endpoint<impl HttpEndpoint> {
+        SwappableRouter::new(self).endpoint_with_config(config, ctx_fn)
+    }
+
+    /// Serve the router with websocket connections running on the async runtime `R`. Eg. `WasmRuntime` for Cloudflare Workers.
+    pub fn endpoint_with_runtime<
+        R: AsyncRuntime,
+        TCtxFnMarker: Send + Sync + 'static,
+        TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
+    >(
+        self: Arc<Self>,
+        config: EndpointConfig,
+        ctx_fn: TCtxFn,
+    ) -> Endpoint<impl HttpEndpoint> {
+        SwappableRouter::new(self).endpoint_with_runtime::<R, _, _>(config, ctx_fn)
+    }
+}
+
+impl<TCtx> SwappableRouter<TCtx>
+where
+    TCtx: Clone + Send + Sync + 'static,
+{
+    /// Serve the router. Swapping the router will apply to all new requests and websocket messages of the endpoint.
+    #[cfg(feature = "tokio")]
+    pub fn endpoint<TCtxFnMarker: Send + Sync + 'static, TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>>(
+        &self,
+        ctx_fn: TCtxFn,
+    ) -> Endpoint<impl HttpEndpoint> {
+        self.endpoint_with_config(EndpointConfig::new(), ctx_fn)
+    }
+
+    #[cfg(feature = "tokio")]
+    pub fn endpoint_with_config<
+        TCtxFnMarker: Send + Sync + 'static,
+        TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
+    >(
+        &self,
+        config: EndpointConfig,
+        ctx_fn: TCtxFn,
+    ) -> Endpoint<impl HttpEndpoint> {
+        self.endpoint_with_runtime::<TokioRuntime, _, _>(config, ctx_fn)
+    }
+
+    /// Serve the router with websocket connections running on the async runtime `R`. Eg. `WasmRuntime` for Cloudflare Workers.
+    pub fn endpoint_with_runtime<
+        R: AsyncRuntime,
+        TCtxFnMarker: Send + Sync + 'static,
+        TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
+    >(
+        &self,
+        config: EndpointConfig,
+        ctx_fn: TCtxFn,
+    ) -> Endpoint<impl HttpEndpoint> {
+        let executor = match &config.recorder {
+            Some(recorder) => Executor::new(self.clone()).with_recorder(recorder.clone()),
+            None => Executor::new(self.clone()),
+        };
+        let config = Arc::new(config);
 
         // TODO: This should be able to call `ctn_fn` prior to the async boundary to avoid cloning it!
         // TODO: Basically httpz would need to be able to return `Response | Future<Response>` basically how rspc executor works.
 
         GenericEndpoint::new(
             "/:id", // TODO: I think this is Axum specific. Fix in `httpz`!
-            [Method::GET, Method::POST],
+            [Method::GET, Method::POST, Method::OPTIONS],
             move |req: httpz::Request| {
                 // TODO: It would be nice if these clones weren't per request.
                 // TODO: Maybe httpz can `Box::leak` a ref to a context type and allow it to be shared.
                 let executor = executor.clone();
                 let ctx_fn = ctx_fn.clone();
+                let config = config.clone();
 
                 async move {
-                    match (req.method(), &req.uri().path()[1..]) {
-                        (&Method::GET, "ws") => {
-                            handle_websocket(executor, ctx_fn, req).into_response()
-                        }
-                        (&Method::GET, _) => {
-                            handle_http(executor, ctx_fn, req).await.into_response()
-                        }
-                        (&Method::POST, "_batch") => handle_http_batch(executor, ctx_fn, req)
-                            .await
-                            .into_response(),
-                        (&Method::POST, _) => {
-                            handle_http(executor, ctx_fn, req).await.into_response()
+                    let origin = req.headers().get(header::ORIGIN).cloned();
+
+                    // This is checked first so requests which claim to be too large are rejected before anything else is done with them.
+                    let too_large = matches!(config.max_body_size, Some(max) if content_length_exceeds(req.headers(), max));
+
+                    let resp = match &config.cors {
+                        _ if too_large => error_response(ExecError::ErrPayloadTooLarge),
+                        Some(cors) if *req.method() == Method::OPTIONS => {
+                            return cors.preflight(origin.as_ref());
                         }
-                        _ => Ok(Response::builder()
-                            .status(StatusCode::METHOD_NOT_ALLOWED)
-                            .body(vec![])?),
+                        _ => handle_request::<R, _, _, _>(executor, ctx_fn, &config, req).await,
+                    };
+
+                    // Rejections need the CORS headers too, otherwise the browser hides their status from the frontend.
+                    match &config.cors {
+                        Some(cors) => resp.map(|mut resp| {
+                            cors.apply(origin.as_ref(), resp.headers_mut());
+                            resp
+                        }),
+                        None => resp,
                     }
                 }
             },
@@ -67,6 +147,97 @@ where
     }
 }
 
+async fn handle_request<R, TCtx, TCtxFn, TCtxFnMarker>(
+    executor: Executor<TCtx>,
+    ctx_fn: TCtxFn,
+    config: &EndpointConfig,
+    req: httpz::Request,
+) -> Result<Response<Vec<u8>>, httpz::Error>
+where
+    R: AsyncRuntime,
+    TCtx: Clone + Send + Sync + 'static,
+    TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
+{
+    // Chunked requests don't have a `Content-Length` so we also have to check the body the webserver read.
+    if matches!(config.max_body_size, Some(max) if req.body().len() > max) {
+        return error_response(ExecError::ErrPayloadTooLarge);
+    }
+
+    if let Some(csrf) = &config.csrf {
+        let result = match (req.method(), &req.uri().path()[1..]) {
+            (&Method::GET, "ws") => csrf.check_websocket(req.headers(), req.uri().query()),
+            (&Method::POST, _) => csrf.check(req.headers()),
+            _ => Ok(()),
+        };
+
+        if let Err(err) = result {
+            return error_response(err);
+        }
+    }
+
+    match (req.method(), &req.uri().path()[1..]) {
+        // The documents are generated from the current router as it may have been swapped.
+        (&Method::GET, "rspc.openapi.json") if config.openapi.is_some() => {
+            let openapi = config
+                .openapi
+                .as_ref()
+                .map(|cfg| executor.router().openapi(cfg))
+                .unwrap_or_default();
+
+            Ok(Response::builder()
+                .status(StatusCode::OK)
+                .header("Content-Type", "application/json")
+                .body(serde_json::to_vec(&openapi).unwrap_or_default())?)
+        }
+        (&Method::GET, "rspc.docs") if config.openapi.is_some() => Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "text/html; charset=utf-8")
+            .body(
+                config
+                    .openapi
+                    .as_ref()
+                    .map(|cfg| cfg.ui().into_bytes())
+                    .unwrap_or_default(),
+            )?),
+        (&Method::GET, "rspc.manifest") if config.manifest => Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .body(serde_json::to_vec(&executor.router().manifest()).unwrap_or_default())?),
+        (&Method::GET, "ws") => {
+            handle_websocket::<R, _, _, _>(executor, ctx_fn, req, config.max_websocket_message_size)
+                .into_response()
+        }
+        (&Method::GET, _) => handle_http(executor, ctx_fn, req).await.into_response(),
+        (&Method::POST, "_batch") => handle_http_batch(executor, ctx_fn, req, config.max_batch_len)
+            .await
+            .into_response(),
+        (&Method::POST, _) => handle_http(executor, ctx_fn, req).await.into_response(),
+        _ => Ok(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body(vec![])?),
+    }
+}
+
+/// Returns `true` if the `Content-Length` header of a request is larger than `max_body_size`.
+fn content_length_exceeds(headers: &HeaderMap, max_body_size: usize) -> bool {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok())
+        .is_some_and(|len| len > max_body_size)
+}
+
+/// Respond to a request that was rejected before it could be executed.
+fn error_response(err: ExecError) -> Result<Response<Vec<u8>>, httpz::Error> {
+    let err: exec::ResponseError = err.into();
+    let status = StatusCode::from_u16(err.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+
+    Ok(Response::builder()
+        .status(status)
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_vec(&exec::ResponseInner::Error(err)).unwrap_or_default())?)
+}
+
 #[allow(clippy::unwrap_used)] // TODO: Remove all panics lol
 async fn handle_http<TCtx, TCtxFn, TCtxFnMarker>(
     executor: Executor<TCtx>,
@@ -77,19 +248,32 @@ where
     TCtx: Send + Sync + 'static,
     TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
 {
-    let path = Cow::Owned(
-        match req.server() {
-            #[cfg(feature = "vercel")]
-            httpz::Server::Vercel => req
-                .query_pairs()
-                .and_then(|mut pairs| pairs.find(|e| e.0 == "rspc"))
-                .map(|(_, v)| v.to_string()),
-            _ => Some(req.uri().path()[1..].to_string()), // Has to be allocated because `TCtxFn` takes ownership of `req`
-        }
-        .unwrap(),
-    );
+    let mut path = match req.server() {
+        #[cfg(feature = "vercel")]
+        httpz::Server::Vercel => req
+            .query_pairs()
+            .and_then(|mut pairs| pairs.find(|e| e.0 == "rspc"))
+            .map(|(_, v)| v.to_string()),
+        _ => Some(req.uri().path()[1..].to_string()), // Has to be allocated because `TCtxFn` takes ownership of `req`
+    }
+    .unwrap();
 
     let cookies = req.cookies();
+
+    // The version is always in the query string so it's the same for queries and mutations.
+    // It's appended to the path so it's resolved the same as a request from the websocket.
+    if let Some(version) = req
+        .query_pairs()
+        .and_then(|mut params| params.find(|e| e.0 == "version").map(|e| e.1))
+    {
+        if let Err(err) = version.parse::<VersionReq>() {
+            return Ok((error_response(ExecError::ErrInvalidVersion(err))?, cookies));
+        }
+
+        path = format!("{path}@{version}");
+    }
+    let path = Cow::Owned(path);
+
     let request = match *req.method() {
         Method::GET => {
             let input = req
@@ -132,6 +316,11 @@ where
         }
     };
 
+    let recorder = executor.recorder().map(|r| (r, r.connection()));
+    if let Some((recorder, conn)) = recorder {
+        recorder.request(conn, &request);
+    }
+
     let response =
         match executor.execute(ctx, request, &mut (None as Option<NoOpSubscriptionManager>)) {
             ExecutorResult::FutureResponse(fut) => fut.await,
@@ -139,7 +328,11 @@ where
             ExecutorResult::None => unreachable!(
                 "Executor will only return none for a 'stopSubscription' event which is impossible here"
             ),
-        }.inner;
+        };
+    if let Some((recorder, conn)) = recorder {
+        recorder.response(conn, &response);
+    }
+    let response = response.inner;
 
     let cookies = {
         match Arc::try_unwrap(cookie_jar) {
@@ -179,13 +372,15 @@ async fn handle_http_batch<TCtx, TCtxFn, TCtxFnMarker>(
     executor: Executor<TCtx>,
     ctx_fn: TCtxFn,
     req: httpz::Request,
+    max_batch_len: Option<usize>,
 ) -> impl HttpResponse
 where
     TCtx: Clone + Send + Sync + 'static,
     TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
 {
     let cookies = req.cookies();
-    match serde_json::from_slice::<Vec<exec::Request>>(req.body()) {
+    match deserialize_batch(req.body(), max_batch_len) {
+        Err(BatchError::TooLong) => Ok((error_response(ExecError::ErrPayloadTooLarge)?, cookies)),
         Ok(requests) => {
             let cookie_jar = Arc::new(Mutex::new(cookies));
             let old_cookies = req.cookies().clone();
@@ -207,6 +402,13 @@ where
                 }
             };
 
+            let recorder = executor.recorder().map(|r| (r, r.connection()));
+            if let Some((recorder, conn)) = recorder {
+                for request in &requests {
+                    recorder.request(conn, request);
+                }
+            }
+
             let fut_responses = FuturesUnordered::new();
             let mut responses = executor.execute_batch(
                 &ctx,
@@ -230,6 +432,11 @@ where
             };
 
             responses.append(&mut fut_responses.collect().await);
+            if let Some((recorder, conn)) = recorder {
+                for response in &responses {
+                    recorder.response(conn, response);
+                }
+            }
             match serde_json::to_vec(&responses) {
                 Ok(v) => Ok((
                     Response::builder()
@@ -252,7 +459,7 @@ where
                 }
             }
         }
-        Err(_err) => {
+        Err(BatchError::Json(_err)) => {
             #[cfg(feature = "tracing")]
             tracing::error!("Error deserializing batch request: {}", _err);
 
@@ -268,3 +475,120 @@ where
         }
     }
 }
+
+enum BatchError {
+    TooLong,
+    Json(serde_json::Error),
+}
+
+/// Deserialize the requests of a `_batch` request.
+///
+/// This stops as soon as the batch has more than `max_batch_len` requests so a large batch is never fully parsed.
+fn deserialize_batch(
+    body: &[u8],
+    max_batch_len: Option<usize>,
+) -> Result<Vec<exec::Request>, BatchError> {
+    let too_long = Cell::new(false);
+    let mut de = serde_json::Deserializer::from_slice(body);
+    let result = BatchSeed {
+        max_batch_len,
+        too_long: &too_long,
+    }
+    .deserialize(&mut de)
+    .and_then(|requests| de.end().map(|_| requests));
+
+    result.map_err(|err| match too_long.get() {
+        true => BatchError::TooLong,
+        false => BatchError::Json(err),
+    })
+}
+
+struct BatchSeed<'a> {
+    max_batch_len: Option<usize>,
+    too_long: &'a Cell<bool>,
+}
+
+impl<'de> DeserializeSeed<'de> for BatchSeed<'_> {
+    type Value = Vec<exec::Request>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for BatchSeed<'_> {
+    type Value = Vec<exec::Request>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of requests")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut requests = Vec::new();
+        while let Some(request) = seq.next_element()? {
+            if matches!(self.max_batch_len, Some(max) if requests.len() >= max) {
+                self.too_long.set(true);
+                return Err(de::Error::custom(
+                    "the batch exceeded the maximum allowed length",
+                ));
+            }
+
+            requests.push(request);
+        }
+
+        Ok(requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use httpz::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_content_length() {
+        let mut headers = HeaderMap::new();
+        assert!(!content_length_exceeds(&headers, 10));
+
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("10"));
+        assert!(!content_length_exceeds(&headers, 10));
+
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("11"));
+        assert!(content_length_exceeds(&headers, 10));
+    }
+
+    #[test]
+    fn test_batch_len() {
+        let request = r#"{"method":"query","id":0,"path":"a","input":null}"#;
+        let batch = |len: usize| format!("[{}]", vec![request; len].join(","));
+
+        assert_eq!(
+            deserialize_batch(batch(2).as_bytes(), Some(2))
+                .ok()
+                .map(|r| r.len()),
+            Some(2)
+        );
+        assert_eq!(
+            deserialize_batch(batch(3).as_bytes(), None)
+                .ok()
+                .map(|r| r.len()),
+            Some(3)
+        );
+        assert!(matches!(
+            deserialize_batch(batch(3).as_bytes(), Some(2)),
+            Err(BatchError::TooLong)
+        ));
+
+        // Parsing stops at the limit so the rest of the body is never looked at
+        let body = format!("[{request},{request},{request},not json");
+        assert!(matches!(
+            deserialize_batch(body.as_bytes(), Some(2)),
+            Err(BatchError::TooLong)
+        ));
+        assert!(matches!(
+            deserialize_batch(body.as_bytes(), None),
+            Err(BatchError::Json(_))
+        ));
+    }
+}
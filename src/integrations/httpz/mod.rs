@@ -2,13 +2,19 @@
 //!
 //! This is done through [httpz](https://github.com/oscartbeaumont/httpz).
 
+mod config;
 mod cookie_jar;
+mod cors;
+mod csrf;
 mod extractors;
 mod httpz_endpoint;
 mod request;
 mod websocket;
 
+pub use config::*;
 pub use cookie_jar::*;
+pub use cors::*;
+pub use csrf::*;
 pub use extractors::*;
 pub use httpz_endpoint::*;
 pub use request::*;
@@ -1,4 +1,10 @@
-use std::pin::pin;
+use std::{
+    pin::pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 use futures::{SinkExt, StreamExt};
 use httpz::{
@@ -7,16 +13,18 @@ use httpz::{
     HttpResponse,
 };
 
-use crate::internal::exec::{ConnectionTask, Executor, IncomingMessage, TokioRuntime};
+use crate::internal::exec::{AsyncRuntime, ConnectionTask, Executor, IncomingMessage};
 
 use super::TCtxFunc;
 
-pub(crate) fn handle_websocket<TCtx, TCtxFn, TCtxFnMarker>(
+pub(crate) fn handle_websocket<R, TCtx, TCtxFn, TCtxFnMarker>(
     executor: Executor<TCtx>,
     ctx_fn: TCtxFn,
     req: httpz::Request,
+    max_message_size: Option<usize>,
 ) -> impl HttpResponse
 where
+    R: AsyncRuntime,
     TCtx: Clone + Send + Sync + 'static,
     TCtxFn: TCtxFunc<TCtx, TCtxFnMarker>,
 {
@@ -48,30 +56,81 @@ where
 
     let cookies = req.cookies(); // TODO: Reorder args of next func so cookies goes first
     WebsocketUpgrade::from_req_with_cookies(req, cookies, move |_, socket| async move {
+        let too_large = Arc::new(AtomicBool::new(false));
         let socket = socket
             .with(|v: String| async move { Ok(Message::Text(v)) as Result<_, httpz::Error> })
-            .map(|v| {
-                v.map(|v| match v {
-                    Message::Text(v) => IncomingMessage::Msg(serde_json::from_str(&v)),
-                    Message::Binary(v) => IncomingMessage::Msg(serde_json::from_slice(&v)),
-                    Message::Ping(_) | Message::Pong(_) => IncomingMessage::Skip,
-                    // TODO: This is a suboptimal feature flag cause it's *akshually* based on Tokio or not.
-                    #[cfg(feature = "axum")]
-                    Message::Close(_) => IncomingMessage::Close,
-                    // TODO: This is a suboptimal feature flag cause it's *akshually* based on Tokio or not.
-                    #[cfg(feature = "axum")]
-                    Message::Frame(_) => {
-                        #[cfg(debug_assertions)]
-                        unreachable!("Reading a 'httpz::ws::Message::Frame' is impossible");
-
-                        #[cfg(not(debug_assertions))]
-                        return IncomingMessage::Skip;
-                    }
-                })
+            .map({
+                let too_large = too_large.clone();
+                move |v| {
+                    v.map(|v| match v {
+                        // We can't correlate an oversized message to a request so the connection is closed.
+                        v if is_too_large(&v, max_message_size) => {
+                            #[cfg(feature = "tracing")]
+                            tracing::debug!(
+                                "Closing websocket connection due to oversized message"
+                            );
+
+                            too_large.store(true, Ordering::Relaxed);
+                            IncomingMessage::Close
+                        }
+                        Message::Text(v) => IncomingMessage::Msg(serde_json::from_str(&v)),
+                        Message::Binary(v) => IncomingMessage::Msg(serde_json::from_slice(&v)),
+                        Message::Ping(_) | Message::Pong(_) => IncomingMessage::Skip,
+                        // TODO: This is a suboptimal feature flag cause it's *akshually* based on Tokio or not.
+                        #[cfg(feature = "axum")]
+                        Message::Close(_) => IncomingMessage::Close,
+                        // TODO: This is a suboptimal feature flag cause it's *akshually* based on Tokio or not.
+                        #[cfg(feature = "axum")]
+                        Message::Frame(_) => {
+                            #[cfg(debug_assertions)]
+                            unreachable!("Reading a 'httpz::ws::Message::Frame' is impossible");
+
+                            #[cfg(not(debug_assertions))]
+                            return IncomingMessage::Skip;
+                        }
+                    })
+                }
             });
-        let socket = pin!(socket);
+        let mut socket = pin!(socket);
+
+        ConnectionTask::<R, _, _, _>::new(ctx, executor, socket.as_mut(), None).await;
 
-        ConnectionTask::<TokioRuntime, _, _, _>::new(ctx, executor, socket, None).await;
+        // TODO: This is a suboptimal feature flag cause it's *akshually* based on Tokio or not.
+        #[cfg(feature = "axum")]
+        if too_large.load(Ordering::Relaxed) {
+            use tungstenite::protocol::{frame::coding::CloseCode, CloseFrame};
+
+            let close = Message::Close(Some(CloseFrame {
+                code: CloseCode::Size,
+                reason: "message too big".into(),
+            }));
+            socket.get_pin_mut().get_pin_mut().send(close).await.ok();
+        }
     })
     .into_response()
 }
+
+/// The websocket library has already read the message so this only stops rspc from handling it.
+fn is_too_large(msg: &Message, max_message_size: Option<usize>) -> bool {
+    let len = match msg {
+        Message::Text(v) => v.len(),
+        Message::Binary(v) => v.len(),
+        _ => return false,
+    };
+
+    matches!(max_message_size, Some(max) if len > max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_size() {
+        assert!(!is_too_large(&Message::Text("a".repeat(10)), None));
+        assert!(!is_too_large(&Message::Text("a".repeat(10)), Some(10)));
+        assert!(is_too_large(&Message::Text("a".repeat(11)), Some(10)));
+        assert!(is_too_large(&Message::Binary(vec![0; 11]), Some(10)));
+        assert!(!is_too_large(&Message::Ping(vec![0; 11]), Some(10)));
+    }
+}
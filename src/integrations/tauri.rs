@@ -18,7 +18,7 @@ use tokio::sync::mpsc;
 
 use crate::{
     internal::exec::{AsyncRuntime, ConnectionTask, Executor, IncomingMessage, TokioRuntime},
-    BuiltRouter,
+    Recorder, SwappableRouter,
 };
 
 struct WindowManager<TCtxFn, TCtx>
@@ -36,9 +36,9 @@ where
     TCtx: Clone + Send + Sync + 'static,
     TCtxFn: Fn(Window<tauri::Wry>) -> TCtx + Send + Sync + 'static,
 {
-    pub fn new(ctx_fn: TCtxFn, router: Arc<BuiltRouter<TCtx>>) -> Arc<Self> {
+    pub fn new(ctx_fn: TCtxFn, executor: Executor<TCtx>) -> Arc<Self> {
         Arc::new(Self {
-            executor: Executor::new(router),
+            executor,
             ctx_fn,
             windows: Mutex::new(HashMap::new()),
         })
@@ -111,16 +111,42 @@ where
 }
 
 pub fn plugin<TCtx>(
-    router: Arc<BuiltRouter<TCtx>>,
+    router: impl Into<SwappableRouter<TCtx>>,
     ctx_fn: impl Fn(Window<tauri::Wry>) -> TCtx + Send + Sync + 'static,
 ) -> TauriPlugin<tauri::Wry>
 where
     TCtx: Clone + Send + Sync + 'static,
 {
-    let manager = WindowManager::new(ctx_fn, router);
+    plugin_with_runtime::<TokioRuntime, _>(Executor::new(router.into()), ctx_fn)
+}
+
+/// Same as [`plugin`] but every request and response sent over the IPC bridge is written to `recorder`.
+pub fn plugin_with_recorder<TCtx>(
+    router: impl Into<SwappableRouter<TCtx>>,
+    ctx_fn: impl Fn(Window<tauri::Wry>) -> TCtx + Send + Sync + 'static,
+    recorder: Recorder,
+) -> TauriPlugin<tauri::Wry>
+where
+    TCtx: Clone + Send + Sync + 'static,
+{
+    plugin_with_runtime::<TokioRuntime, _>(
+        Executor::new(router.into()).with_recorder(recorder),
+        ctx_fn,
+    )
+}
+
+/// Same as [`plugin`] but windows are served by `executor` on the async runtime `R` instead of Tauri's Tokio runtime.
+pub fn plugin_with_runtime<R: AsyncRuntime, TCtx>(
+    executor: Executor<TCtx>,
+    ctx_fn: impl Fn(Window<tauri::Wry>) -> TCtx + Send + Sync + 'static,
+) -> TauriPlugin<tauri::Wry>
+where
+    TCtx: Clone + Send + Sync + 'static,
+{
+    let manager = WindowManager::new(ctx_fn, executor);
     Builder::new("rspc")
         .on_page_load(move |window, _page| {
-            manager.clone().on_page_load::<TokioRuntime>(window.clone());
+            manager.clone().on_page_load::<R>(window.clone());
 
             window.on_window_event({
                 let window = window.clone();
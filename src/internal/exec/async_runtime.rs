@@ -1,4 +1,7 @@
-use std::{future::Future, time::Instant};
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
 
 /// Define an async runtime.
 pub trait AsyncRuntime: Sync + Send + 'static {
@@ -17,6 +20,28 @@ pub trait AsyncRuntime: Sync + Send + 'static {
 
     /// returns a future that waits until an instant to resolve.
     fn sleep_util(till: Instant) -> Self::SleepUtilFut;
+
+    /// returns the current time which deadlines passed to `Self::sleep_util` are calculated from.
+    fn now() -> Instant {
+        Instant::now()
+    }
+
+    /// returns a future that resolves after a duration. This is what rspc uses internally.
+    ///
+    /// Runtimes on targets without [`Instant`] support (eg. `wasm32-unknown-unknown`) should override this.
+    fn sleep(duration: Duration) -> Self::SleepUtilFut {
+        Self::sleep_util(Self::now() + duration)
+    }
+}
+
+/// A runtime which polls and drops every task on the thread it was spawned from.
+///
+/// Only these runtimes can be used with [`Local`](crate::Local) as it would panic if it was moved to another thread.
+pub trait SingleThreaded: AsyncRuntime + Sized {
+    /// Wrap a `!Send` future or stream, eg. one which holds an `Rc` across an `.await`, so it can be returned from a resolver. Refer to [`Local`](crate::Local).
+    fn local<T>(value: T) -> crate::Local<T, Self> {
+        crate::Local::new(value)
+    }
 }
 
 #[cfg(feature = "tokio")]
@@ -42,7 +67,172 @@ mod tokio_runtime {
             tokio::time::sleep_until(till.into())
         }
     }
+
+    /// Support for running on a single thread using a Tokio [`LocalSet`](tokio::task::LocalSet), so `!Send` state can be used with [`Local`](crate::Local).
+    ///
+    /// Tasks are spawned using [`tokio::task::spawn_local`] so this must be used from within a `LocalSet`.
+    pub struct LocalRuntime {}
+
+    impl AsyncRuntime for LocalRuntime {
+        type TaskHandle = tokio::task::JoinHandle<()>;
+        type SleepUtilFut = tokio::time::Sleep;
+
+        fn spawn<F: Future<Output = ()> + Send + 'static>(f: F) -> Self::TaskHandle {
+            tokio::task::spawn_local(f)
+        }
+
+        fn cancel_task(task: Self::TaskHandle) {
+            task.abort()
+        }
+
+        fn sleep_util(till: Instant) -> Self::SleepUtilFut {
+            tokio::time::sleep_until(till.into())
+        }
+    }
+
+    impl SingleThreaded for LocalRuntime {}
 }
 
 #[cfg(feature = "tokio")]
 pub use tokio_runtime::*;
+
+#[cfg(any(feature = "async-std", feature = "smol"))]
+mod async_io_timer {
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use super::*;
+
+    /// A timer from [async-io](https://docs.rs/async-io) which is used by both async-std and smol.
+    pub struct AsyncIoSleep(async_io::Timer);
+
+    impl AsyncIoSleep {
+        pub(crate) fn at(till: Instant) -> Self {
+            Self(async_io::Timer::at(till))
+        }
+    }
+
+    impl Future for AsyncIoSleep {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            Pin::new(&mut self.0).poll(cx).map(|_| ())
+        }
+    }
+}
+
+#[cfg(any(feature = "async-std", feature = "smol"))]
+pub use async_io_timer::*;
+
+#[cfg(feature = "async-std")]
+mod async_std_runtime {
+    use futures::future::{AbortHandle, Abortable};
+
+    use super::*;
+
+    /// Support for the [async-std](https://async.rs/) async runtime.
+    pub struct AsyncStdRuntime {}
+
+    impl AsyncRuntime for AsyncStdRuntime {
+        // Dropping an async-std `JoinHandle` detaches the task so it's wrapped to make it abortable.
+        type TaskHandle = AbortHandle;
+        type SleepUtilFut = AsyncIoSleep;
+
+        fn spawn<F: Future<Output = ()> + Send + 'static>(f: F) -> Self::TaskHandle {
+            let (handle, registration) = AbortHandle::new_pair();
+            async_std::task::spawn(async move {
+                Abortable::new(f, registration).await.ok();
+            });
+            handle
+        }
+
+        fn cancel_task(task: Self::TaskHandle) {
+            task.abort()
+        }
+
+        fn sleep_util(till: Instant) -> Self::SleepUtilFut {
+            AsyncIoSleep::at(till)
+        }
+    }
+}
+
+#[cfg(feature = "async-std")]
+pub use async_std_runtime::*;
+
+#[cfg(feature = "smol")]
+mod smol_runtime {
+    use super::*;
+
+    /// Support for the [smol](https://github.com/smol-rs/smol) async runtime. Tasks are spawned onto its global executor.
+    pub struct SmolRuntime {}
+
+    impl AsyncRuntime for SmolRuntime {
+        type TaskHandle = smol::Task<()>;
+        type SleepUtilFut = AsyncIoSleep;
+
+        fn spawn<F: Future<Output = ()> + Send + 'static>(f: F) -> Self::TaskHandle {
+            smol::spawn(f)
+        }
+
+        fn cancel_task(task: Self::TaskHandle) {
+            // Dropping a smol `Task` cancels it
+            drop(task)
+        }
+
+        fn sleep_util(till: Instant) -> Self::SleepUtilFut {
+            AsyncIoSleep::at(till)
+        }
+    }
+}
+
+#[cfg(feature = "smol")]
+pub use smol_runtime::*;
+
+#[cfg(feature = "wasm")]
+mod wasm_runtime {
+    use futures::future::{AbortHandle, Abortable};
+    use gloo_timers::future::TimeoutFuture;
+    use send_wrapper::SendWrapper;
+
+    use super::*;
+
+    /// Support for single-threaded WebAssembly targets, eg. Cloudflare Workers. Tasks are spawned onto the JavaScript event loop.
+    ///
+    /// [`Instant`] is not supported on `wasm32-unknown-unknown` so rspc only calls [`AsyncRuntime::sleep`] on this runtime.
+    pub struct WasmRuntime {}
+
+    impl AsyncRuntime for WasmRuntime {
+        type TaskHandle = AbortHandle;
+        // JavaScript timers are `!Send` but wasm is single-threaded so they can never be sent to another thread.
+        type SleepUtilFut = SendWrapper<TimeoutFuture>;
+
+        fn spawn<F: Future<Output = ()> + Send + 'static>(f: F) -> Self::TaskHandle {
+            let (handle, registration) = AbortHandle::new_pair();
+            wasm_bindgen_futures::spawn_local(async move {
+                Abortable::new(f, registration).await.ok();
+            });
+            handle
+        }
+
+        fn cancel_task(task: Self::TaskHandle) {
+            task.abort()
+        }
+
+        // rspc never calls this. A caller can only pass an `Instant` if the target supports it, eg. `wasm32-wasi`, so `Instant::now` is safe here.
+        fn sleep_util(till: Instant) -> Self::SleepUtilFut {
+            Self::sleep(till.saturating_duration_since(Instant::now()))
+        }
+
+        fn sleep(duration: Duration) -> Self::SleepUtilFut {
+            let millis = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+            SendWrapper::new(TimeoutFuture::new(millis))
+        }
+    }
+
+    impl SingleThreaded for WasmRuntime {}
+}
+
+#[cfg(feature = "wasm")]
+pub use wasm_runtime::*;
@@ -4,7 +4,7 @@ use std::{
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
-    time::{Duration, Instant},
+    time::Duration,
 };
 
 use futures::{ready, Sink, Stream};
@@ -16,15 +16,18 @@ use super::{
     AsyncRuntime, Executor, IncomingMessage, OwnedStream, Request, Response, StreamOrFut,
     SubscriptionManager, SubscriptionSet,
 };
-use crate::internal::{
-    exec::{self, ResponseInner},
-    PinnedOption, PinnedOptionProj,
+use crate::{
+    internal::{
+        exec::{self, ResponseInner},
+        PinnedOption, PinnedOptionProj,
+    },
+    Recorder,
 };
 
 // Time to wait for more messages before sending them over the websocket connection.
 // This batch is mostly designed to reduce the impact of duplicate subscriptions a bit
 // as sending them together should help us utilise transport layer compression.
-const BATCH_TIMEOUT: Duration = Duration::from_millis(5);
+pub(crate) const BATCH_TIMEOUT: Duration = Duration::from_millis(5);
 
 enum PollResult {
     /// The poller has done some progressed work.
@@ -48,7 +51,10 @@ struct ConnectionSubscriptionManager<'a, TCtx> {
 impl<'a, TCtx: Clone + Send + 'static> SubscriptionManager<TCtx>
     for ConnectionSubscriptionManager<'a, TCtx>
 {
-    type Set<'m> = &'m mut SubscriptionSet where Self: 'm;
+    type Set<'m>
+        = &'m mut SubscriptionSet
+    where
+        Self: 'm;
 
     fn queue(&mut self, stream: OwnedStream<TCtx>) {
         match &mut self.queued {
@@ -81,8 +87,7 @@ impl<R: AsyncRuntime> Batcher<R> {
     fn insert(self: Pin<&mut Self>, element: exec::Response) {
         let mut this = self.project();
         this.batch.push(element);
-        this.batch_timer
-            .set(R::sleep_util(Instant::now() + BATCH_TIMEOUT).into());
+        this.batch_timer.set(R::sleep(BATCH_TIMEOUT).into());
     }
 
     fn append(self: Pin<&mut Self>, other: &mut Vec<exec::Response>) {
@@ -92,8 +97,7 @@ impl<R: AsyncRuntime> Batcher<R> {
 
         let mut this = self.project();
         this.batch.append(other);
-        this.batch_timer
-            .set(R::sleep_util(Instant::now() + BATCH_TIMEOUT).into());
+        this.batch_timer.set(R::sleep(BATCH_TIMEOUT).into());
     }
 }
 
@@ -108,6 +112,9 @@ pin_project! {
 
         // TODO: Remove these cause disgusting messes
         sub_id_to_stream: HashMap<u32, usize>,
+
+        // The recorder of the executor and the id it assigned to this connection.
+        recorder: Option<(Recorder, u64)>,
     }
 }
 
@@ -116,6 +123,12 @@ where
     TCtx: Clone + Send + 'static,
 {
     pub fn exec(&mut self, reqs: Vec<Request>) -> Vec<Response> {
+        if let Some((recorder, conn_id)) = &self.recorder {
+            for req in &reqs {
+                recorder.request(*conn_id, req);
+            }
+        }
+
         let mut manager = Some(ConnectionSubscriptionManager {
             map: &mut self.map,
             to_abort: None,
@@ -148,6 +161,12 @@ where
             }
         }
 
+        if let Some((recorder, conn_id)) = &self.recorder {
+            for resp in &resps {
+                recorder.response(*conn_id, resp);
+            }
+        }
+
         resps
     }
 }
@@ -199,6 +218,10 @@ impl<
         socket: S,
         clear_subscriptions_rx: ClearSubscriptionsRx,
     ) -> Self {
+        let recorder = executor
+            .recorder()
+            .map(|recorder| (recorder.clone(), recorder.connection()));
+
         Self {
             conn: Connection {
                 ctx,
@@ -206,6 +229,7 @@ impl<
                 map: SubscriptionSet::new(),
                 streams: StreamUnordered::new(),
                 sub_id_to_stream: HashMap::new(),
+                recorder,
             },
             batch: Batcher {
                 batch: Vec::with_capacity(4),
@@ -331,6 +355,9 @@ impl<
             match ready!(conn.streams.as_mut().poll_next(cx)) {
                 Some((a, _)) => match a {
                     StreamYield::Item(resp) => {
+                        if let Some((recorder, conn_id)) = conn.recorder.as_ref() {
+                            recorder.response(*conn_id, &resp);
+                        }
                         this.batch.as_mut().insert(resp);
                         return PollResult::QueueSend.into();
                     }
@@ -19,9 +19,9 @@ mod private {
         internal::{
             exec::{AsyncRuntime, OwnedStream, Request, Response, ResponseInner},
             middleware::{ProcedureKind, RequestContext},
-            FutureValueOrStream, ProcedureStore, ProcedureTodo,
+            FutureValueOrStream, ProcedureStore,
         },
-        BuiltRouter, ExecError,
+        BuiltRouter, ExecError, Recorder, SwappableRouter,
     };
 
     /// Map for subscription id to task handle.
@@ -80,21 +80,53 @@ mod private {
     /// TODO
     pub struct Executor<TCtx> {
         // TODO: Not `pub`
-        pub(crate) router: Arc<BuiltRouter<TCtx>>,
+        pub(crate) router: SwappableRouter<TCtx>,
+        recorder: Option<Recorder>,
     }
 
     impl<TCtx: Send + 'static> Clone for Executor<TCtx> {
         fn clone(&self) -> Self {
             Self {
                 router: self.router.clone(),
+                recorder: self.recorder.clone(),
             }
         }
     }
 
     impl<TCtx: Send + 'static> Executor<TCtx> {
         /// constructs a new [Executor] for your router.
-        pub fn new(router: Arc<BuiltRouter<TCtx>>) -> Self {
-            Self { router }
+        ///
+        /// Pass a [`SwappableRouter`] to be able to replace the router while the executor is running.
+        pub fn new(router: impl Into<SwappableRouter<TCtx>>) -> Self {
+            Self {
+                router: router.into(),
+                recorder: None,
+            }
+        }
+
+        /// Record the requests and responses of every connection which uses this executor. Refer to [`Recorder`].
+        pub fn with_recorder(self, recorder: Recorder) -> Self {
+            Self {
+                recorder: Some(recorder),
+                ..self
+            }
+        }
+
+        /// The recorder attached with [`Executor::with_recorder`].
+        pub fn recorder(&self) -> Option<&Recorder> {
+            self.recorder.as_ref()
+        }
+
+        /// The router which new requests are executed against.
+        pub fn router(&self) -> Arc<BuiltRouter<TCtx>> {
+            self.router.load()
+        }
+
+        /// Replace the router of this executor and every clone of it. Returns the previous router.
+        ///
+        /// Requests and subscriptions which are already running will finish using the previous router.
+        pub fn swap_router(&self, router: Arc<BuiltRouter<TCtx>>) -> Arc<BuiltRouter<TCtx>> {
+            self.router.swap(router)
         }
 
         /// TODO
@@ -156,13 +188,13 @@ mod private {
             match req {
                 Request::Query { id, path, input } => ExecRequestFut::exec(
                     ctx,
-                    &self.router.queries,
+                    &self.router.current(),
                     RequestContext::new(id, ProcedureKind::Query, path),
                     input,
                 ),
                 Request::Mutation { id, path, input } => ExecRequestFut::exec(
                     ctx,
-                    &self.router.mutations,
+                    &self.router.current(),
                     RequestContext::new(id, ProcedureKind::Mutation, path),
                     input,
                 ),
@@ -205,7 +237,7 @@ mod private {
             }
 
             let id = req.id;
-            match OwnedStream::new(self.router.clone(), ctx, input, req) {
+            match OwnedStream::new(&self.router.current(), ctx, input, req) {
                 Ok(s) => {
                     subscriptions.insert(id);
                     drop(subscriptions);
@@ -214,39 +246,47 @@ mod private {
 
                     ExecutorResult::None
                 }
-                Err(id) => ExecutorResult::Response(Response {
+                Err(err) => ExecutorResult::Response(Response {
                     id,
-                    inner: ResponseInner::Error(ExecError::OperationNotFound.into()),
+                    inner: ResponseInner::Error(err.into()),
                 }),
             }
         }
     }
 
     pub struct ExecRequestFut {
-        stream: Pin<Box<dyn Stream<Item = Result<Value, ExecError>> + Send>>,
+        // This owns the procedure it was created from so the request keeps running if the router is swapped or dropped.
+        stream: FutureValueOrStream,
         pub id: u32,
     }
 
     impl ExecRequestFut {
-        pub fn exec<TCtx: 'static>(
+        pub fn exec<TCtx: Send + 'static>(
             ctx: TCtx,
-            procedures: *const ProcedureStore<TCtx>,
-            req: RequestContext,
+            router: &BuiltRouter<TCtx>,
+            mut req: RequestContext,
             input: Option<Value>,
         ) -> ExecutorResult {
-            // TODO: This unsafe is not coupled to the Arc which is bad
-            match unsafe { &*procedures }.store.get(req.path.as_ref()) {
-                Some(procedure) => ExecutorResult::FutureResponse(Self {
-                    id: req.id,
-                    stream: procedure
-                        .exec
-                        .dyn_call(ctx, input.unwrap_or(Value::Null), req),
-                }),
-                None => ExecutorResult::Response(Response {
-                    id: req.id,
-                    inner: ResponseInner::Error(ExecError::OperationNotFound.into()),
-                }),
-            }
+            let procedure = match router.store(req.kind).resolve(&req.path) {
+                Ok((key, procedure)) => {
+                    if key != req.path {
+                        req.path = Cow::Owned(key.to_string());
+                    }
+                    procedure
+                }
+                Err(err) => {
+                    return ExecutorResult::Response(Response {
+                        id: req.id,
+                        inner: ResponseInner::Error(err.into()),
+                    })
+                }
+            };
+            req.meta = Some(procedure.meta.clone());
+
+            ExecutorResult::FutureResponse(Self {
+                id: req.id,
+                stream: procedure.call(ctx, input, req),
+            })
         }
     }
 
@@ -1,12 +1,8 @@
 mod private {
     use std::{
         borrow::Cow,
-        future::Future,
         marker::PhantomData,
-        mem::transmute,
-        ops::Deref,
         pin::Pin,
-        sync::Arc,
         task::{Context, Poll},
     };
 
@@ -15,7 +11,7 @@ mod private {
     use serde_json::Value;
 
     use crate::{
-        internal::{middleware::RequestContext, ProcedureTodo},
+        internal::{middleware::RequestContext, FutureValueOrStream},
         BuiltRouter, ExecError,
     };
 
@@ -23,38 +19,35 @@ mod private {
 
     pin_project! {
         #[project = OwnedStreamProj]
-        /// TODO
+        /// The stream of a running subscription.
+        ///
+        /// It owns the procedure it was created from so it doesn't borrow the router and can outlive it, eg. if the router is swapped.
         pub struct OwnedStream<TCtx> {
-            arc: Arc<BuiltRouter<TCtx>>,
             #[pin]
-            reference: Pin<Box<dyn Stream<Item = Result<Value, ExecError>> + Send>>,
+            reference: FutureValueOrStream,
             pub id: u32,
+            phantom: PhantomData<fn() -> TCtx>,
         }
     }
 
     impl<TCtx: 'static> OwnedStream<TCtx> {
         pub(crate) fn new(
-            router: Arc<BuiltRouter<TCtx>>,
+            router: &BuiltRouter<TCtx>,
             ctx: TCtx,
             input: Option<Value>,
-            req: RequestContext,
-        ) -> Result<Self, u32> {
-            let stream: *const _ = match router.subscriptions.store.get(req.path.as_ref()) {
-                Some(v) => v,
-                None => return Err(req.id),
-            };
+            mut req: RequestContext,
+        ) -> Result<Self, ExecError> {
+            let (key, procedure) = router.subscriptions.resolve(&req.path)?;
+            if key != req.path {
+                req.path = Cow::Owned(key.to_string());
+            }
+            req.meta = Some(procedure.meta.clone());
 
             let id = req.id;
-
-            // SAFETY: Trust me bro
-            let stream = unsafe { &*stream }
-                .exec
-                .dyn_call(ctx, input.unwrap_or(Value::Null), req);
-
             Ok(Self {
-                arc: router,
-                reference: stream,
+                reference: procedure.call(ctx, input, req),
                 id,
+                phantom: PhantomData,
             })
         }
     }
@@ -72,36 +65,14 @@ mod private {
     }
 }
 
-use std::{
-    borrow::Cow,
-    future::Future,
-    ops::Deref,
-    pin::Pin,
-    sync::Arc,
-    task::{Context, Poll},
-};
-
-use futures::Stream;
 #[cfg(feature = "unstable")]
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
 pub use private::OwnedStream;
 
 #[cfg(not(feature = "unstable"))]
 pub(crate) use private::OwnedStream;
-use serde_json::Value;
-
-use crate::{
-    internal::{
-        middleware::{ProcedureKind, RequestContext},
-        ProcedureStore,
-    },
-    ExecError,
-};
 
-use super::{
-    AsyncRuntime, ExecRequestFut, Executor, ExecutorResult, Request, Response, ResponseInner,
-    SubscriptionManager,
-};
+use super::{Executor, ExecutorResult, Request, SubscriptionManager};
 
 // TODO: Seal the following stuff
 
@@ -109,19 +80,12 @@ use super::{
 //
 // TODO: Rename
 pub struct TrustMeBro<TCtx: Send + 'static> {
-    #[allow(unused)]
     arc: Executor<TCtx>,
-    queries: *const ProcedureStore<TCtx>,
-    mutations: *const ProcedureStore<TCtx>,
 }
 
 impl<TCtx: Send + 'static> TrustMeBro<TCtx> {
     pub fn new(arc: Executor<TCtx>) -> Self {
-        Self {
-            queries: &arc.router.queries,
-            mutations: &arc.router.mutations,
-            arc,
-        }
+        Self { arc }
     }
 
     /// TODO
@@ -133,82 +97,9 @@ impl<TCtx: Send + 'static> TrustMeBro<TCtx> {
         &self,
         ctx: TCtx,
         req: Request,
-        mut subscription_manager: &mut Option<M>,
+        subscription_manager: &mut Option<M>,
     ) -> ExecutorResult {
-        // TODO
-        // #[cfg(feature = "tracing")]
-        // tracing::debug!(
-        //     "Executing operation '{}' with key '{}' with params {:?}",
-        //     kind.to_str(),
-        //     procedure_name,
-        //     input
-        // );
-
-        match req {
-            Request::Query { id, path, input } => ExecRequestFut::exec(
-                ctx,
-                unsafe { &*self.queries },
-                RequestContext::new(id, ProcedureKind::Query, path),
-                input,
-            ),
-            Request::Mutation { id, path, input } => ExecRequestFut::exec(
-                ctx,
-                unsafe { &*self.mutations },
-                RequestContext::new(id, ProcedureKind::Mutation, path),
-                input,
-            ),
-            Request::Subscription { id, path, input } => match subscription_manager {
-                Some(subscriptions) => self.exec_subscription(
-                    ctx,
-                    subscriptions,
-                    RequestContext::new(id, ProcedureKind::Subscription, path),
-                    input,
-                ),
-                None => ExecutorResult::Response(Response {
-                    id,
-                    inner: ResponseInner::Error(ExecError::ErrSubscriptionsNotSupported.into()),
-                }),
-            },
-            Request::SubscriptionStop { id } => {
-                if let Some(subscriptions) = &mut subscription_manager {
-                    subscriptions.abort_subscription(id);
-                }
-
-                ExecutorResult::None
-            }
-        }
-    }
-
-    fn exec_subscription<M: SubscriptionManager<TCtx>>(
-        &self,
-        ctx: TCtx,
-        subscription_manager: &mut M,
-        req: RequestContext,
-        input: Option<Value>,
-    ) -> ExecutorResult {
-        let mut subscriptions = subscription_manager.subscriptions();
-
-        if subscriptions.contains(&req.id) {
-            return ExecutorResult::Response(Response {
-                id: req.id,
-                inner: ResponseInner::Error(ExecError::ErrSubscriptionDuplicateId.into()),
-            });
-        }
-
-        let id = req.id;
-        match OwnedStream::new(self.arc.router.clone(), ctx, input, req) {
-            Ok(s) => {
-                subscriptions.insert(id);
-                drop(subscriptions);
-
-                subscription_manager.queue(s);
-
-                ExecutorResult::None
-            }
-            Err(id) => ExecutorResult::Response(Response {
-                id,
-                inner: ResponseInner::Error(ExecError::OperationNotFound.into()),
-            }),
-        }
+        // The procedures were previously cached here but that isn't possible now the router can be swapped.
+        self.arc.execute(ctx, req, subscription_manager)
     }
 }
@@ -7,7 +7,7 @@ mod private {
     /// The type of a request to rspc.
     ///
     /// @internal
-    #[derive(Debug, Deserialize)]
+    #[derive(Debug, Serialize, Deserialize)]
     #[cfg_attr(test, derive(specta::Type))]
     #[serde(tag = "method", rename_all = "camelCase")]
     pub enum Request {
@@ -42,7 +42,7 @@ mod private {
     /// An error that can be returned by rspc.
     ///
     /// @internal
-    #[derive(Debug, Serialize, PartialEq, Eq)]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
     #[cfg_attr(test, derive(specta::Type))]
     pub struct ResponseError {
         pub code: u16,
@@ -53,7 +53,7 @@ mod private {
     /// A value that can be a successful result or an error.
     ///
     /// @internal
-    #[derive(Debug, Serialize, PartialEq, Eq)]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
     #[cfg_attr(test, derive(specta::Type))]
     #[serde(tag = "type", content = "value", rename_all = "camelCase")]
     pub enum ResponseInner {
@@ -68,7 +68,7 @@ mod private {
     /// The type of a response from rspc.
     ///
     /// @internal
-    #[derive(Debug, Serialize, PartialEq, Eq)]
+    #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
     #[cfg_attr(test, derive(specta::Type))]
     #[serde(rename_all = "camelCase")]
     pub struct Response {
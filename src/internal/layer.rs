@@ -1,4 +1,4 @@
-use std::{future::ready, pin::Pin};
+use std::{future::ready, pin::Pin, sync::Arc};
 
 use futures::{stream::once, Stream};
 use serde_json::Value;
@@ -6,8 +6,7 @@ use serde_json::Value;
 use crate::ExecError;
 
 // TODO: Make this an enum so it can be `Value || Pin<Box<dyn Stream>>`?
-pub(crate) type FutureValueOrStream<'a> =
-    Pin<Box<dyn Stream<Item = Result<Value, ExecError>> + Send + 'a>>;
+pub(crate) type FutureValueOrStream = Pin<Box<dyn Stream<Item = Result<Value, ExecError>> + Send>>;
 
 #[doc(hidden)]
 pub trait Layer<TLayerCtx: 'static>: SealedLayer<TLayerCtx> {}
@@ -19,20 +18,20 @@ mod private {
 
     pub trait DynLayer<TLayerCtx: 'static>: Send + Sync + 'static {
         fn dyn_call(
-            &self,
+            self: Arc<Self>,
             ctx: TLayerCtx,
             input: Value,
             req: RequestContext,
-        ) -> FutureValueOrStream<'_>;
+        ) -> FutureValueOrStream;
     }
 
     impl<TLayerCtx: Send + 'static, L: Layer<TLayerCtx>> DynLayer<TLayerCtx> for L {
         fn dyn_call(
-            &self,
+            self: Arc<Self>,
             ctx: TLayerCtx,
             input: Value,
             req: RequestContext,
-        ) -> FutureValueOrStream<'_> {
+        ) -> FutureValueOrStream {
             match self.call(ctx, input, req) {
                 Ok(stream) => Box::pin(stream),
                 Err(err) => Box::pin(once(ready(Err(err)))),
@@ -41,25 +40,42 @@ mod private {
     }
 
     /// Prevents the end user implementing the `Layer` trait and hides the internals
+    ///
+    /// Layers are called through an `Arc` so the stream they return can own the layers after it instead of borrowing them.
+    /// This allows a request to outlive the router it was started on, eg. when the router is swapped.
     pub trait SealedLayer<TLayerCtx: 'static>: DynLayer<TLayerCtx> {
-        type Stream<'a>: Stream<Item = Result<Value, ExecError>> + Send + 'a;
+        type Stream: Stream<Item = Result<Value, ExecError>> + Send + 'static;
 
         fn call(
-            &self,
+            self: Arc<Self>,
             ctx: TLayerCtx,
             input: Value,
             req: RequestContext,
-        ) -> Result<Self::Stream<'_>, ExecError>;
+        ) -> Result<Self::Stream, ExecError>;
 
-        fn erase(self) -> Box<dyn DynLayer<TLayerCtx>>
+        fn erase(self) -> Arc<dyn DynLayer<TLayerCtx>>
         where
             Self: Sized,
         {
-            Box::new(self)
+            Arc::new(self)
         }
     }
 
-    impl<TLayerCtx: 'static, L: SealedLayer<TLayerCtx>> Layer<TLayerCtx> for L {}
+    impl<TLayerCtx: 'static, L: SealedLayer<TLayerCtx> + ?Sized> Layer<TLayerCtx> for L {}
+
+    // Allows an already built procedure to be wrapped in more layers, eg. by `Router::with`.
+    impl<TLayerCtx: Send + 'static> SealedLayer<TLayerCtx> for dyn DynLayer<TLayerCtx> {
+        type Stream = FutureValueOrStream;
+
+        fn call(
+            self: Arc<Self>,
+            ctx: TLayerCtx,
+            input: Value,
+            req: RequestContext,
+        ) -> Result<Self::Stream, ExecError> {
+            Ok(self.dyn_call(ctx, input, req))
+        }
+    }
 }
 
 pub(crate) use private::{DynLayer, SealedLayer};
@@ -6,7 +6,7 @@ mod private {
 
     use crate::internal::{middleware::SealedMiddlewareBuilder, Layer};
 
-    pub struct BaseMiddleware<TCtx>(PhantomData<TCtx>);
+    pub struct BaseMiddleware<TCtx>(PhantomData<fn() -> TCtx>);
 
     impl<TCtx> Default for BaseMiddleware<TCtx> {
         fn default() -> Self {
@@ -16,7 +16,7 @@ mod private {
 
     impl<TCtx> SealedMiddlewareBuilder for BaseMiddleware<TCtx>
     where
-        TCtx: Send + Sync + 'static,
+        TCtx: Send + 'static,
     {
         type Ctx = TCtx;
         type LayerCtx = TCtx;
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::{
+    internal::{middleware::RequestContext, DynLayer, FutureValueOrStream, SealedLayer},
+    Error, ExecError,
+};
+
+/// Converts the context of every request before calling a procedure which was defined against a different context. Used by `Router::merge_with`.
+pub(crate) struct MapCtxLayer<TChildCtx, F> {
+    pub(crate) next: Arc<dyn DynLayer<TChildCtx>>,
+    pub(crate) map: Arc<F>,
+}
+
+impl<TCtx, TChildCtx, F> SealedLayer<TCtx> for MapCtxLayer<TChildCtx, F>
+where
+    TCtx: Send + 'static,
+    TChildCtx: 'static,
+    F: Fn(TCtx) -> Result<TChildCtx, Error> + Send + Sync + 'static,
+{
+    type Stream = FutureValueOrStream;
+
+    fn call(
+        self: Arc<Self>,
+        ctx: TCtx,
+        input: Value,
+        req: RequestContext,
+    ) -> Result<Self::Stream, ExecError> {
+        let ctx = (self.map)(ctx).map_err(ExecError::ErrMappingContext)?;
+        Ok(self.next.clone().dyn_call(ctx, input, req))
+    }
+}
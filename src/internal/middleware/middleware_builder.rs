@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use serde::de::DeserializeOwned;
 use specta::Type;
@@ -15,8 +15,8 @@ mod private {
     use super::*;
 
     pub trait SealedMiddlewareBuilder: Send + 'static {
-        type Ctx: Send + Sync + 'static;
-        type LayerCtx: Send + Sync + 'static;
+        type Ctx: Send + 'static;
+        type LayerCtx: Send + 'static;
         type Arg<T: Type + DeserializeOwned + 'static>: Type + DeserializeOwned + 'static;
 
         type LayerResult<T>: Layer<Self::Ctx>
@@ -42,7 +42,7 @@ mod private {
     impl<TLayerCtx, TMiddleware, TNewMiddleware> SealedMiddlewareBuilder
         for MiddlewareLayerBuilder<TMiddleware, TNewMiddleware>
     where
-        TLayerCtx: Send + Sync + 'static,
+        TLayerCtx: Send + 'static,
         TMiddleware: MiddlewareBuilder<LayerCtx = TLayerCtx> + Send + Sync + 'static,
         TNewMiddleware: Middleware<TLayerCtx> + Send + Sync + 'static,
     {
@@ -58,7 +58,7 @@ mod private {
             T: Layer<Self::LayerCtx> + Sync,
         {
             self.middleware.build(MiddlewareLayer {
-                next,
+                next: Arc::new(next),
                 mw: self.mw,
                 phantom: PhantomData,
             })
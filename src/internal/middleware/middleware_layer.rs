@@ -2,6 +2,7 @@ mod private {
     use std::{
         marker::PhantomData,
         pin::Pin,
+        sync::Arc,
         task::{ready, Context, Poll},
     };
 
@@ -19,48 +20,50 @@ mod private {
     };
 
     #[doc(hidden)]
-    pub struct MiddlewareLayer<TLayerCtx, TMiddleware, TNewMiddleware> {
-        pub(crate) next: TMiddleware,
+    pub struct MiddlewareLayer<TLayerCtx, TMiddleware: ?Sized, TNewMiddleware> {
+        pub(crate) next: Arc<TMiddleware>,
         pub(crate) mw: TNewMiddleware,
-        pub(crate) phantom: PhantomData<TLayerCtx>,
+        pub(crate) phantom: PhantomData<fn() -> TLayerCtx>,
     }
 
     impl<TLayerCtx, TMiddleware, TNewMiddleware> SealedLayer<TLayerCtx>
         for MiddlewareLayer<TLayerCtx, TMiddleware, TNewMiddleware>
     where
-        TLayerCtx: Send + Sync + 'static,
-        TMiddleware: Layer<TNewMiddleware::NewCtx> + Sync + 'static,
+        TLayerCtx: Send + 'static,
+        TMiddleware: Layer<TNewMiddleware::NewCtx> + Sync + ?Sized + 'static,
         TNewMiddleware: Middleware<TLayerCtx> + Send + Sync + 'static,
     {
-        type Stream<'a> = MiddlewareLayerFuture<'a, TLayerCtx, TNewMiddleware, TMiddleware>;
+        type Stream = MiddlewareLayerFuture<TLayerCtx, TNewMiddleware, TMiddleware>;
 
         fn call(
-            &self,
+            self: Arc<Self>,
             ctx: TLayerCtx,
             input: Value,
             req: RequestContext,
-        ) -> Result<Self::Stream<'_>, ExecError> {
+        ) -> Result<Self::Stream, ExecError> {
             let fut = self.mw.run_me(ctx, MiddlewareContext::new(input, req));
 
             Ok(MiddlewareLayerFuture::Resolve {
                 fut,
-                next: &self.next,
+                next: self.next.clone(),
             })
         }
     }
 
     // This exists because `pin_project_lite` doesn't understand `+` bounds
-    pub trait SendSyncStatic: Send + Sync + 'static {}
-    impl<T: Send + Sync + 'static> SendSyncStatic for T {}
+    pub trait SendStatic: Send + 'static {}
+    impl<T: Send + 'static> SendStatic for T {}
 
     pin_project! {
         #[project = MiddlewareLayerFutureProj]
         pub enum MiddlewareLayerFuture<
-            'a,
-            TLayerCtx: SendSyncStatic,
+            TLayerCtx: SendStatic,
             TMiddleware: Middleware<TLayerCtx>,
+            TNextLayer: ?Sized,
+        >
+        where
             TNextLayer: Layer<TMiddleware::NewCtx>,
-        > {
+        {
             // We are waiting for the current middleware to run and yield it's result.
             // Remember the middleware only runs once for an entire stream as it returns "instructions" on how to map the stream from then on.
             Resolve {
@@ -71,13 +74,13 @@ mod private {
 
                 // The next layer in the middleware chain
                 // This could be another middleware of the users resolver. It will be called to yield the `stream` for the next phase.
-                next: &'a TNextLayer,
+                next: Arc<TNextLayer>,
             },
             // We are in this state where we are executing the current middleware on the stream
             Execute {
                 // The actual data stream from the resolver function or next middleware
                 #[pin]
-                stream: TNextLayer::Stream<'a>,
+                stream: TNextLayer::Stream,
                 // We use this so we can keep polling `resp_fut` for the final message and once it is done and this bool is set, shutdown.
                 is_stream_done: bool,
 
@@ -98,11 +101,10 @@ mod private {
     }
 
     impl<
-            'a,
-            TLayerCtx: Send + Sync + 'static,
+            TLayerCtx: Send + 'static,
             TMiddleware: Middleware<TLayerCtx>,
-            TNextLayer: Layer<TMiddleware::NewCtx>,
-        > Stream for MiddlewareLayerFuture<'a, TLayerCtx, TMiddleware, TNextLayer>
+            TNextLayer: Layer<TMiddleware::NewCtx> + ?Sized,
+        > Stream for MiddlewareLayerFuture<TLayerCtx, TMiddleware, TNextLayer>
     {
         type Item = Result<Value, ExecError>;
 
@@ -120,7 +122,7 @@ mod private {
                             }
                         };
 
-                        match next.call(ctx, input, req) {
+                        match next.clone().call(ctx, input, req) {
                             Ok(stream) => {
                                 self.as_mut().set(Self::Execute {
                                     stream,
@@ -1,6 +1,7 @@
 //! TODO: Module docs
 
 mod base_middleware;
+mod map_ctx_layer;
 mod middleware_builder;
 mod middleware_layer;
 mod mw;
@@ -9,6 +10,7 @@ mod mw_result;
 mod resolver_layer;
 
 pub(crate) use base_middleware::*;
+pub(crate) use map_ctx_layer::*;
 pub use middleware_builder::*;
 pub(crate) use middleware_layer::*;
 pub use mw::*;
@@ -1,4 +1,4 @@
-use std::future::Future;
+use std::{future::Future, sync::Arc};
 
 use serde::de::DeserializeOwned;
 use specta::Type;
@@ -16,13 +16,13 @@ pub trait Middleware<TLCtx>: SealedMiddleware<TLCtx> {}
 pub trait ConstrainedMiddleware<TLCtx>:
     Middleware<TLCtx> + Fn(MiddlewareContext, TLCtx) -> Self::Fut + Send + Sync + 'static
 where
-    TLCtx: Send + Sync + 'static,
+    TLCtx: Send + 'static,
 {
 }
 
 impl<TLCtx, F, Fu, R> ConstrainedMiddleware<TLCtx> for F
 where
-    TLCtx: Send + Sync + 'static,
+    TLCtx: Send + 'static,
     F: Fn(MiddlewareContext, TLCtx) -> Fu + Send + Sync + 'static,
     Fu: Future<Output = R> + Send + 'static,
     R: MwV2Result + Send + 'static,
@@ -35,7 +35,7 @@ mod private {
     pub trait SealedMiddleware<TLCtx>: Send + Sync + 'static {
         type Fut: Future<Output = Self::Result> + Send + 'static;
         type Result: MwV2Result<Ctx = Self::NewCtx>;
-        type NewCtx: Send + Sync + 'static;
+        type NewCtx: Send + 'static;
         type Arg<T: Type + DeserializeOwned + 'static>: Type + DeserializeOwned + 'static;
 
         // TODO: Rename
@@ -46,7 +46,7 @@ mod private {
 
     impl<TLCtx, F, Fu, R> SealedMiddleware<TLCtx> for F
     where
-        TLCtx: Send + Sync + 'static,
+        TLCtx: Send + 'static,
         F: Fn(MiddlewareContext, TLCtx) -> Fu + Send + Sync + 'static,
         Fu: Future<Output = R> + Send + 'static,
         R: MwV2Result + Send + 'static,
@@ -60,8 +60,33 @@ mod private {
             self(mw, ctx)
         }
     }
+
+    /// A middleware which is shared between many procedures, eg. by `Router::with`.
+    pub struct SharedMiddleware<TMiddleware>(pub(crate) Arc<TMiddleware>);
+
+    impl<TMiddleware> Clone for SharedMiddleware<TMiddleware> {
+        fn clone(&self) -> Self {
+            Self(self.0.clone())
+        }
+    }
+
+    impl<TLCtx, TMiddleware> SealedMiddleware<TLCtx> for SharedMiddleware<TMiddleware>
+    where
+        TMiddleware: SealedMiddleware<TLCtx>,
+    {
+        type Fut = TMiddleware::Fut;
+        type Result = TMiddleware::Result;
+        type NewCtx = TMiddleware::NewCtx;
+        type Arg<T: Type + DeserializeOwned + 'static> = TMiddleware::Arg<T>;
+
+        fn run_me(&self, ctx: TLCtx, mw: MiddlewareContext) -> Self::Fut {
+            self.0.run_me(ctx, mw)
+        }
+    }
 }
 
+pub(crate) use private::SharedMiddleware;
+
 #[cfg(feature = "unstable")]
 pub use private::SealedMiddleware;
 
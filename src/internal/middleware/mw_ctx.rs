@@ -1,7 +1,10 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, sync::Arc};
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::ProcedureMeta;
+
 use super::{Executable2Placeholder, MwResultWithCtx};
 
 pub struct MiddlewareContext {
@@ -39,7 +42,8 @@ impl MiddlewareContext {
 /// TODO
 // TODO: Is this a duplicate of any type?
 // TODO: Move into public API cause it might be used in middleware
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ProcedureKind {
     Query,
     Mutation,
@@ -63,6 +67,7 @@ pub struct RequestContext {
     pub id: u32,
     pub kind: ProcedureKind,
     pub path: Cow<'static, str>,
+    pub(crate) meta: Option<Arc<ProcedureMeta>>,
     #[cfg(feature = "tracing")]
     span: Option<Option<tracing::Span>>,
     // Prevents downstream user constructing type
@@ -77,10 +82,16 @@ impl RequestContext {
             span: None,
             kind,
             path,
+            meta: None,
             _priv: (),
         }
     }
 
+    /// The metadata attached to the procedure being executed.
+    pub fn meta(&self) -> Option<&ProcedureMeta> {
+        self.meta.as_deref()
+    }
+
     #[cfg(feature = "tracing")]
     pub fn span(&self) -> Option<tracing::Span> {
         self.span.clone().unwrap_or_else(|| {
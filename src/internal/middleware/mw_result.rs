@@ -44,7 +44,7 @@ impl Executable2 for Executable2Placeholder {
 
 // #[deprecated = "TODO: We probs have to remove this. Sadge!"] // TODO: Deal with this type and seal it
 pub trait MwV2Result {
-    type Ctx: Send + Sync + 'static;
+    type Ctx: Send + 'static;
     type Resp: Executable2;
 
     // TODO: Seal this and make it private
@@ -72,7 +72,7 @@ impl<TLCtx, TResp: Executable2> MwResultWithCtx<TLCtx, TResp> {
 
 impl<TLCtx, TResp> MwV2Result for MwResultWithCtx<TLCtx, TResp>
 where
-    TLCtx: Send + Sync + 'static,
+    TLCtx: Send + 'static,
     TResp: Executable2,
 {
     type Ctx = TLCtx;
@@ -85,7 +85,7 @@ where
 
 impl<TLCtx, TResp> MwV2Result for Result<MwResultWithCtx<TLCtx, TResp>, crate::Error>
 where
-    TLCtx: Send + Sync + 'static,
+    TLCtx: Send + 'static,
     TResp: Executable2,
 {
     type Ctx = TLCtx;
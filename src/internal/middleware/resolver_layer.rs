@@ -1,4 +1,4 @@
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use futures::Stream;
 use serde::de::DeserializeOwned;
@@ -27,23 +27,23 @@ impl<T, TArg> ResolverLayer<T, TArg> {
 // TODO: For `T: ResolverFunction` or something like that to simplify the generics
 impl<T, TArg, TLayerCtx, S> SealedLayer<TLayerCtx> for ResolverLayer<T, TArg>
 where
-    TLayerCtx: Send + Sync + 'static,
+    TLayerCtx: Send + 'static,
     TArg: Type + DeserializeOwned + 'static,
     T: Fn(TLayerCtx, TArg, RequestContext) -> Result<S, ExecError> + Send + Sync + 'static,
     S: Stream<Item = Result<Value, ExecError>> + Send + 'static,
 {
     #[cfg(feature = "tracing")]
-    type Stream<'a> = futures::future::Either<S, tracing_futures::Instrumented<S>>;
+    type Stream = futures::future::Either<S, tracing_futures::Instrumented<S>>;
 
     #[cfg(not(feature = "tracing"))]
-    type Stream<'a> = S;
+    type Stream = S;
 
     fn call(
-        &self,
+        self: Arc<Self>,
         ctx: TLayerCtx,
         input: Value,
         req: RequestContext,
-    ) -> Result<Self::Stream<'_>, ExecError> {
+    ) -> Result<Self::Stream, ExecError> {
         #[cfg(feature = "tracing")]
         let span = req.span();
 
@@ -52,7 +52,8 @@ where
 
         let result = (self.func)(
             ctx,
-            serde_json::from_value(input).map_err(ExecError::DeserializingArgErr)?,
+            serde_path_to_error::deserialize(input)
+                .map_err(|err| ExecError::ErrValidation(err.into()))?,
             req,
         );
 
@@ -1,6 +1,14 @@
+use std::{panic::Location, sync::Arc};
+
 use specta::TypeDefs;
 
-use crate::internal::ProcedureStore;
+use crate::{
+    internal::{
+        middleware::ProcedureKind, DynLayer, Layer, ProcedureDataType, ProcedureStore,
+        ProcedureTodo,
+    },
+    BuildError, BuildErrorCause, ProcedureMeta,
+};
 
 // TODO: Make this `pub(crate)` instead of sealed.
 mod private {
@@ -12,6 +20,93 @@ mod private {
         pub(crate) queries: &'a mut ProcedureStore<TCtx>,
         pub(crate) mutations: &'a mut ProcedureStore<TCtx>,
         pub(crate) subscriptions: &'a mut ProcedureStore<TCtx>,
+        pub(crate) errors: &'a mut Vec<BuildError>,
+        // The location of the procedure which is currently being built.
+        pub(crate) loc: &'static Location<'static>,
+        // The position of the procedure which is currently being built in the order procedures were registered.
+        pub(crate) index: usize,
     }
 }
 pub(crate) use private::*;
+
+impl<'a, TCtx: 'static> BuildProceduresCtx<'a, TCtx> {
+    fn store(&mut self, kind: ProcedureKind) -> &mut ProcedureStore<TCtx> {
+        match kind {
+            ProcedureKind::Query => self.queries,
+            ProcedureKind::Mutation => self.mutations,
+            ProcedureKind::Subscription => self.subscriptions,
+        }
+    }
+
+    pub(crate) fn append<L: Layer<TCtx>>(
+        &mut self,
+        kind: ProcedureKind,
+        key: String,
+        exec: L,
+        ty: ProcedureDataType,
+        meta: ProcedureMeta,
+    ) {
+        let procedure = ProcedureTodo {
+            exec: exec.erase(),
+            ty,
+            meta: Arc::new(meta),
+            input_schema: None,
+            loc: self.loc,
+            index: self.index,
+        };
+
+        self.insert(kind, key, procedure);
+    }
+
+    fn insert(&mut self, kind: ProcedureKind, key: String, procedure: ProcedureTodo<TCtx>) {
+        if let Err(existing) = self.store(kind).insert(key.clone(), procedure) {
+            self.errors.push(BuildError {
+                cause: BuildErrorCause::DuplicateKey(kind, existing),
+                name: key.into(),
+                loc: self.loc,
+            });
+        }
+    }
+
+    /// Build procedures which are defined against a different context and convert their layers into layers which take this context using `map`.
+    ///
+    /// The types are exported into the same type store so they are shared with the rest of the router.
+    pub(crate) fn build_mapped<TChildCtx: 'static>(
+        &mut self,
+        build: impl FnOnce(&mut BuildProceduresCtx<'_, TChildCtx>),
+        map: impl Fn(Arc<dyn DynLayer<TChildCtx>>) -> Arc<dyn DynLayer<TCtx>>,
+    ) {
+        let mut queries = ProcedureStore::new(self.queries.name);
+        let mut mutations = ProcedureStore::new(self.mutations.name);
+        let mut subscriptions = ProcedureStore::new(self.subscriptions.name);
+
+        build(&mut BuildProceduresCtx {
+            ty_store: self.ty_store,
+            queries: &mut queries,
+            mutations: &mut mutations,
+            subscriptions: &mut subscriptions,
+            errors: self.errors,
+            loc: self.loc,
+            index: self.index,
+        });
+
+        for (kind, from) in [
+            (ProcedureKind::Query, queries),
+            (ProcedureKind::Mutation, mutations),
+            (ProcedureKind::Subscription, subscriptions),
+        ] {
+            for (key, procedure) in from.store {
+                let procedure = ProcedureTodo {
+                    exec: map(procedure.exec),
+                    ty: procedure.ty,
+                    meta: procedure.meta,
+                    input_schema: procedure.input_schema,
+                    loc: procedure.loc,
+                    index: procedure.index,
+                };
+
+                self.insert(kind, key, procedure);
+            }
+        }
+    }
+}
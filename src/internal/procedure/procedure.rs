@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use specta::Type;
 
 use crate::{
@@ -13,7 +14,7 @@ use crate::{
         FutureMarkerType, HasResolver, ProcedureDataType, RequestLayer, ResolverFunction,
         StreamMarkerType,
     },
-    ExecError,
+    ExecError, ProcedureMeta, Validate,
 };
 
 /// TODO: Explain
@@ -26,9 +27,12 @@ impl Default for MissingResolver {
 }
 
 mod private {
+    use crate::ProcedureMeta;
+
     pub struct Procedure<T, TMiddleware> {
         pub(crate) resolver: T,
         pub(crate) mw: TMiddleware,
+        pub(crate) meta: ProcedureMeta,
     }
 }
 
@@ -39,7 +43,35 @@ where
     TMiddleware: MiddlewareBuilder,
 {
     pub(crate) fn new(resolver: T, mw: TMiddleware) -> Self {
-        Self { resolver, mw }
+        Self {
+            resolver,
+            mw,
+            meta: ProcedureMeta::default(),
+        }
+    }
+
+    /// Describe what the procedure does. This is exported as a JSDoc comment in the Typescript bindings.
+    pub fn description(mut self, description: impl Into<Cow<'static, str>>) -> Self {
+        self.meta.description = Some(description.into());
+        self
+    }
+
+    /// Tag the procedure so it can be grouped with related procedures.
+    pub fn tag(mut self, tag: impl Into<Cow<'static, str>>) -> Self {
+        self.meta.tags.push(tag.into());
+        self
+    }
+
+    /// Mark the procedure as deprecated with the reason why.
+    pub fn deprecated(mut self, reason: impl Into<Cow<'static, str>>) -> Self {
+        self.meta.deprecated = Some(reason.into());
+        self
+    }
+
+    /// Attach an arbitrary value to the procedure. This is useful for passing information to your middleware.
+    pub fn meta(mut self, key: impl Into<Cow<'static, str>>, value: impl Into<Value>) -> Self {
+        self.meta.extra.insert(key.into(), value.into());
+        self
     }
 }
 
@@ -50,7 +82,11 @@ macro_rules! resolver {
             R: ResolverFunction<TMiddleware::LayerCtx, RMarker>,
             R::Result: RequestLayer<R::RequestMarker, Type = $result_marker>,
         {
-            Procedure::new(resolver.into_marker(ProcedureKind::$kind), self.mw)
+            Procedure {
+                resolver: resolver.into_marker(ProcedureKind::$kind),
+                mw: self.mw,
+                meta: self.meta,
+            }
         }
     };
 }
@@ -69,14 +105,15 @@ where
         self,
         mw: Mw,
     ) -> Procedure<MissingResolver, MiddlewareLayerBuilder<TMiddleware, Mw>> {
-        Procedure::new(
-            MissingResolver::default(),
-            MiddlewareLayerBuilder {
+        Procedure {
+            resolver: MissingResolver::default(),
+            mw: MiddlewareLayerBuilder {
                 // todo: enforce via typestate
                 middleware: self.mw,
                 mw,
             },
-        )
+            meta: self.meta,
+        }
     }
 
     #[cfg(feature = "unstable")]
@@ -84,14 +121,15 @@ where
         self,
         mw: Mw,
     ) -> Procedure<MissingResolver, MiddlewareLayerBuilder<TMiddleware, Mw>> {
-        Procedure::new(
-            MissingResolver::default(),
-            MiddlewareLayerBuilder {
+        Procedure {
+            resolver: MissingResolver::default(),
+            mw: MiddlewareLayerBuilder {
                 // todo: enforce via typestate
                 middleware: self.mw,
                 mw,
             },
-        )
+            meta: self.meta,
+        }
     }
 }
 
@@ -104,18 +142,27 @@ where
     TResultMarker: 'static,
     TMiddleware: MiddlewareBuilder,
 {
+    /// Validate the input using its [`Validate`] implementation before the resolver is called.
+    ///
+    /// If validation fails the resolver will not be called and the client will receive a `BadRequest` error containing the invalid fields.
+    pub fn validate(self) -> Self
+    where
+        TArg: Validate,
+    {
+        let HasResolver(resolver, kind, _, phantom) = self.resolver;
+        Procedure {
+            resolver: HasResolver(resolver, kind, Some(TArg::validate), phantom),
+            mw: self.mw,
+            meta: self.meta,
+        }
+    }
+
     pub(crate) fn build(
         self,
         key: Cow<'static, str>,
         ctx: &mut BuildProceduresCtx<'_, TMiddleware::Ctx>,
     ) {
-        let HasResolver(resolver, kind, _) = self.resolver;
-
-        let m = match kind {
-            ProcedureKind::Query => &mut ctx.queries,
-            ProcedureKind::Mutation => &mut ctx.mutations,
-            ProcedureKind::Subscription => &mut ctx.subscriptions,
-        };
+        let HasResolver(resolver, kind, validator, _) = self.resolver;
 
         let key_str = key.to_string();
         let type_def = ProcedureDataType::from_tys::<TMiddleware::Arg<TArg>, TResult::Result>(
@@ -124,12 +171,19 @@ where
         )
         .expect("error exporting types"); // TODO: Error handling using `#[track_caller]`
 
-        m.append(
+        ctx.append(
+            kind,
             key_str,
-            self.mw.build(ResolverLayer::new(move |ctx, input, _| {
-                Ok((resolver)(ctx, input).exec())
-            })),
+            self.mw
+                .build(ResolverLayer::new(move |ctx, input: TArg, _| {
+                    if let Some(validator) = validator {
+                        validator(&input).map_err(ExecError::ErrValidation)?;
+                    }
+
+                    Ok((resolver)(ctx, input).exec())
+                })),
             type_def,
+            self.meta,
         );
     }
 }
@@ -1,11 +1,19 @@
 // TODO: Probs unseal a heap of this
 
 mod private {
-    use std::{borrow::Cow, collections::BTreeMap};
+    use std::{borrow::Cow, collections::BTreeMap, future::ready, panic::Location, sync::Arc};
+
+    use futures::stream::once;
+    use serde_json::Value;
 
     use specta::{ts::TsExportError, DataType, DataTypeFrom, DefOpts, Type, TypeDefs};
 
-    use crate::internal::{DynLayer, Layer};
+    use crate::{
+        internal::{middleware::RequestContext, DynLayer, FutureValueOrStream},
+        json_schema::InputSchema,
+        version::parse_versioned_key,
+        ExecError, ProcedureMeta, VersionReq,
+    };
 
     /// Represents a Typescript procedure file which is generated by the Rust code.
     /// This is codegenerated Typescript file is how we can validate the types on the frontend match Rust.
@@ -53,15 +61,44 @@ mod private {
 
     // TODO: Rename this
     pub struct ProcedureTodo<TCtx> {
-        pub(crate) exec: Box<dyn DynLayer<TCtx>>,
+        // This is an `Arc` so the stream of a request can keep the procedure alive without borrowing the router.
+        pub(crate) exec: Arc<dyn DynLayer<TCtx>>,
         pub(crate) ty: ProcedureDataType,
+        // This is an `Arc` so it can be cheaply handed to the `RequestContext` of every request.
+        pub(crate) meta: Arc<ProcedureMeta>,
+        // Set by `BuiltRouter::validate_input_schemas`
+        pub(crate) input_schema: Option<InputSchema>,
+        // Where the procedure was defined. Used to report build errors.
+        pub(crate) loc: &'static Location<'static>,
+        // The position of the procedure in the order procedures were registered with the router. Used to report build errors.
+        pub(crate) index: usize,
     }
 
-    impl<TCtx> ProcedureTodo<TCtx> {
+    impl<TCtx: 'static> ProcedureTodo<TCtx> {
         #[cfg(feature = "unstable")]
         pub fn ty(&self) -> &ProcedureDataType {
             &self.ty
         }
+
+        #[cfg(feature = "unstable")]
+        pub fn meta(&self) -> &ProcedureMeta {
+            &self.meta
+        }
+
+        pub(crate) fn call(
+            &self,
+            ctx: TCtx,
+            input: Option<Value>,
+            req: RequestContext,
+        ) -> FutureValueOrStream {
+            let input = input.unwrap_or(Value::Null);
+
+            if let Some(Err(err)) = self.input_schema.as_ref().map(|s| s.check(&input)) {
+                return Box::pin(once(ready(Err(ExecError::ErrValidation(err)))));
+            }
+
+            self.exec.clone().dyn_call(ctx, input, req)
+        }
     }
 
     pub struct ProcedureStore<TCtx> {
@@ -77,44 +114,67 @@ mod private {
             }
         }
 
-        // TODO: Using track caller style thing for the panics in this function
-        pub(crate) fn append<L: Layer<TCtx>>(
+        /// Add a procedure to the store. If a procedure with the same key already exists the location it was defined at is returned.
+        pub(crate) fn insert(
             &mut self,
             key: String,
-            exec: L,
-            ty: ProcedureDataType,
-        ) {
-            // TODO: Cleanup this logic and do better router merging
-            #[allow(clippy::panic)]
-            if key.is_empty() || key == "ws" || key.starts_with("rpc.") || key.starts_with("rspc.")
-            {
-                panic!(
-                    "rspc error: attempted to create {} operation named '{}', however this name is not allowed.",
-                    self.name,
-                    key
-                );
+            procedure: ProcedureTodo<TCtx>,
+        ) -> Result<(), &'static Location<'static>> {
+            if let Some(existing) = self.store.get(&key) {
+                return Err(existing.loc);
             }
 
-            #[allow(clippy::panic)]
-            if self.store.contains_key(&key) {
-                panic!(
-                    "rspc error: {} operation already has resolver with name '{}'",
-                    self.name, key
-                );
+            self.store.insert(key, procedure);
+            Ok(())
+        }
+
+        /// Find the procedure a request for `path` should be executed against. `path` may end with a [`VersionReq`], eg. `users.get@v1..v3`.
+        /// Returns its full key as it may be a specific version of the procedure.
+        pub(crate) fn resolve(
+            &self,
+            path: &str,
+        ) -> Result<(&str, &ProcedureTodo<TCtx>), ExecError> {
+            let exact = |key: &str| {
+                self.store
+                    .get_key_value(key)
+                    .map(|(key, p)| (key.as_str(), p))
+            };
+            if let Some(procedure) = exact(path) {
+                return Ok(procedure);
             }
 
-            self.store.insert(
-                key,
-                ProcedureTodo {
-                    exec: exec.erase(),
-                    ty,
-                },
-            );
+            let (name, version) = match path.rsplit_once('@') {
+                Some((name, version)) => (
+                    name,
+                    version
+                        .parse::<VersionReq>()
+                        .map_err(ExecError::ErrInvalidVersion)?,
+                ),
+                None => (path, VersionReq::Latest),
+            };
+
+            let prefix = format!("{name}@");
+            let latest = self
+                .store
+                .range(prefix.clone()..)
+                .take_while(|(key, _)| key.starts_with(&prefix))
+                .filter(|(key, _)| {
+                    parse_versioned_key(key).is_some_and(|(n, v)| n == name && version.matches(v))
+                })
+                // The keys are sorted as strings so `v10` comes before `v9`.
+                .max_by_key(|(key, _)| parse_versioned_key(key).map(|(_, v)| v))
+                .map(|(key, p)| (key.as_str(), p));
+
+            match version {
+                VersionReq::Latest => latest.or_else(|| exact(name)),
+                _ => latest,
+            }
+            .ok_or(ExecError::OperationNotFound)
         }
     }
 }
 
-use crate::BuildErrorCause;
+use crate::{version::parse_versioned_key, BuildErrorCause};
 
 pub(crate) fn is_valid_name(name: &str) -> Option<BuildErrorCause> {
     if name.is_empty() || name.len() > 255 {
@@ -127,11 +187,20 @@ pub(crate) fn is_valid_name(name: &str) -> Option<BuildErrorCause> {
         }
     }
 
-    if name == "rspc" || name == "_batch" {
+    // These would collide with the routes of the integrations
+    if matches!(name, "rspc" | "rpc" | "ws" | "_batch") {
         return Some(BuildErrorCause::ReservedName(name.to_string()));
     }
 
     None
 }
 
+/// Validate the key of a procedure which may have a version suffix, eg. `get@v2`.
+pub(crate) fn is_valid_procedure_key(key: &str) -> Option<BuildErrorCause> {
+    match parse_versioned_key(key) {
+        Some((name, _)) => is_valid_name(name),
+        None => is_valid_name(key),
+    }
+}
+
 pub(crate) use private::{ProcedureDataType, ProcedureStore, ProcedureTodo};
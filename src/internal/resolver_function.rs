@@ -12,7 +12,7 @@ pub trait ResolverFunction<TLCtx, TMarker>:
 }
 
 mod private {
-    use crate::internal::middleware::ProcedureKind;
+    use crate::{internal::middleware::ProcedureKind, ValidationError};
 
     use super::*;
 
@@ -31,6 +31,7 @@ mod private {
     pub struct HasResolver<A, B, C, D, E>(
         pub(crate) A,
         pub(crate) ProcedureKind,
+        pub(crate) Option<fn(&C) -> Result<(), ValidationError>>,
         pub(crate) PhantomData<(B, C, D, E)>,
     );
 
@@ -56,7 +57,7 @@ mod private {
     where
         TArg: DeserializeOwned + Type + 'static,
         TResult: RequestLayer<TResultMarker>,
-        TLayerCtx: Send + Sync + 'static,
+        TLayerCtx: Send + 'static,
     {
         type Arg = TArg;
         type RequestMarker = TResultMarker;
@@ -66,7 +67,7 @@ mod private {
             self,
             kind: ProcedureKind,
         ) -> HasResolver<F, TLayerCtx, TArg, TResult, TResultMarker> {
-            HasResolver(self, kind, PhantomData)
+            HasResolver(self, kind, None, PhantomData)
         }
     }
 }
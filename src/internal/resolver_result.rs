@@ -167,7 +167,7 @@ mod private {
     pub enum StreamMarker {}
     impl<TStream, T> SealedRequestLayer<StreamMarker> for TStream
     where
-        TStream: Stream<Item = T> + Send + Sync + 'static,
+        TStream: Stream<Item = T> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
@@ -186,7 +186,7 @@ mod private {
     pub enum ResultStreamMarker {}
     impl<TStream, T> SealedRequestLayer<ResultStreamMarker> for Result<TStream, Error>
     where
-        TStream: Stream<Item = T> + Send + Sync + 'static,
+        TStream: Stream<Item = T> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
@@ -210,7 +210,7 @@ mod private {
     pub enum StreamResultMarker {}
     impl<TStream, T> SealedRequestLayer<StreamResultMarker> for TStream
     where
-        TStream: Stream<Item = Result<T, Error>> + Send + Sync + 'static,
+        TStream: Stream<Item = Result<T, Error>> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
@@ -230,7 +230,7 @@ mod private {
     impl<TFut, TStream, T> SealedRequestLayer<FutureStreamMarker> for TFut
     where
         TFut: Future<Output = TStream> + Send + 'static,
-        TStream: Stream<Item = T> + Send + Sync + 'static,
+        TStream: Stream<Item = T> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
@@ -251,7 +251,7 @@ mod private {
     impl<TFut, TStream, T> SealedRequestLayer<FutureResultStreamMarker> for TFut
     where
         TFut: Future<Output = Result<TStream, Error>> + Send + 'static,
-        TStream: Stream<Item = T> + Send + Sync + 'static,
+        TStream: Stream<Item = T> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
@@ -272,7 +272,7 @@ mod private {
     impl<TFut, TStream, T> SealedRequestLayer<FutureStreamResultMarker> for TFut
     where
         TFut: Future<Output = TStream> + Send + 'static,
-        TStream: Stream<Item = Result<T, Error>> + Send + Sync + 'static,
+        TStream: Stream<Item = Result<T, Error>> + Send + 'static,
         T: Serialize + Type,
     {
         type Result = T;
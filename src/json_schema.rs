@@ -0,0 +1,465 @@
+//! Convert Specta [`DataType`]s into JSON Schema (draft 2020-12) which is also the schema dialect used by OpenAPI 3.1.
+//!
+//! References to named types are never inlined. They become `{ref_prefix}{name}` so the caller decides where the definitions live.
+//! Named types which Specta has already inlined, eg. with `#[specta(inline)]`, stay inlined.
+
+use std::{fs, io, path::PathBuf, sync::Arc};
+
+use serde_json::{json, Map, Value};
+use specta::{
+    DataType, EnumRepr, EnumType, EnumVariant, LiteralType, NamedDataType, NamedDataTypeItem,
+    ObjectType, PrimitiveType, TupleType, TypeDefs,
+};
+
+use crate::{internal::ProcedureStore, BuiltRouter, ExportError, ValidationError};
+
+const DEFS_PREFIX: &str = "#/$defs/";
+
+impl<TCtx> BuiltRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    /// Generate a [JSON Schema (draft 2020-12)](https://json-schema.org/draft/2020-12/json-schema-core) document for the router.
+    ///
+    /// Every named type is defined in `$defs` and the `input` and `result` schemas of each procedure are listed under `queries`, `mutations` and `subscriptions`.
+    pub fn json_schema(&self) -> Value {
+        fn procedures<TCtx>(store: &ProcedureStore<TCtx>) -> Map<String, Value> {
+            store
+                .store
+                .iter()
+                .map(|(key, procedure)| {
+                    (
+                        key.clone(),
+                        json!({
+                            "input": datatype(&procedure.ty.input, DEFS_PREFIX),
+                            "result": datatype(&procedure.ty.result, DEFS_PREFIX),
+                        }),
+                    )
+                })
+                .collect()
+        }
+
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$defs": definitions(&self.typ_store, DEFS_PREFIX),
+            "queries": procedures(&self.queries),
+            "mutations": procedures(&self.mutations),
+            "subscriptions": procedures(&self.subscriptions),
+        })
+    }
+
+    /// Write the document generated by [`BuiltRouter::json_schema`] to a file.
+    pub fn export_json_schema(&self, export_path: impl Into<PathBuf>) -> Result<(), ExportError> {
+        let export_path = export_path.into();
+        if let Some(export_dir) = export_path.parent() {
+            fs::create_dir_all(export_dir)?;
+        }
+
+        let file = fs::File::create(export_path)?;
+        serde_json::to_writer_pretty(file, &self.json_schema()).map_err(io::Error::from)?;
+        Ok(())
+    }
+
+    /// Validate the input of every procedure against its JSON Schema before it's deserialized.
+    ///
+    /// Requests which don't match are rejected with a `BadRequest` error containing a [`ValidationError`] for every mismatched field, instead of only the first error Serde runs into.
+    /// This is useful when the procedures are called by clients which don't use the generated bindings.
+    pub fn validate_input_schemas(mut self) -> Self {
+        let defs = Arc::new(definitions(&self.typ_store, DEFS_PREFIX));
+
+        for store in [
+            &mut self.queries,
+            &mut self.mutations,
+            &mut self.subscriptions,
+        ] {
+            for procedure in store.store.values_mut() {
+                procedure.input_schema = Some(InputSchema {
+                    schema: datatype(&procedure.ty.input, DEFS_PREFIX),
+                    defs: defs.clone(),
+                });
+            }
+        }
+
+        self
+    }
+}
+
+/// The JSON Schema of a procedure's input used to validate requests at runtime.
+pub(crate) struct InputSchema {
+    schema: Value,
+    defs: Arc<Map<String, Value>>,
+}
+
+impl InputSchema {
+    pub(crate) fn check(&self, input: &Value) -> Result<(), ValidationError> {
+        let mut err = ValidationError::new();
+        validate(&self.schema, input, &self.defs, "", &[], &mut err);
+        err.into_result()
+    }
+}
+
+/// Convert every named type in the [`TypeDefs`] into a JSON Schema keyed by its name.
+pub(crate) fn definitions(typ_store: &TypeDefs, ref_prefix: &str) -> Map<String, Value> {
+    typ_store
+        .values()
+        .filter_map(|ty| ty.as_ref())
+        .map(|ty| (ty.name.to_string(), named_datatype(ty, ref_prefix)))
+        .collect()
+}
+
+/// Convert a [`DataType`] into a JSON Schema.
+pub(crate) fn datatype(ty: &DataType, ref_prefix: &str) -> Value {
+    match ty {
+        DataType::Any | DataType::Generic(_) => json!({}),
+        DataType::Primitive(ty) => primitive(ty),
+        DataType::Literal(ty) => literal(ty),
+        DataType::List(ty) => json!({
+            "type": "array",
+            "items": datatype(ty, ref_prefix),
+        }),
+        DataType::Nullable(ty) => json!({
+            "anyOf": [datatype(ty, ref_prefix), { "type": "null" }],
+        }),
+        DataType::Record(ty) => json!({
+            "type": "object",
+            "additionalProperties": datatype(&ty.1, ref_prefix),
+        }),
+        DataType::Named(ty) => named_datatype(ty, ref_prefix),
+        DataType::Object(ty) => object(ty, ref_prefix),
+        DataType::Enum(ty) => enum_type(ty, ref_prefix),
+        DataType::Tuple(ty) => tuple(ty, ref_prefix),
+        // JSON Schema has no way of expressing generic arguments so we reference the definition and the generics become `{}`.
+        DataType::Reference(ty) => json!({ "$ref": format!("{ref_prefix}{}", ty.name) }),
+    }
+}
+
+/// Convert the definition of a named type (as stored in the `TypeDefs`) into a JSON Schema.
+pub(crate) fn named_datatype(ty: &NamedDataType, ref_prefix: &str) -> Value {
+    let mut schema = match &ty.item {
+        NamedDataTypeItem::Object(ty) => object(ty, ref_prefix),
+        NamedDataTypeItem::Enum(ty) => enum_type(ty, ref_prefix),
+        NamedDataTypeItem::Tuple(ty) => tuple(ty, ref_prefix),
+    };
+
+    if let Value::Object(schema) = &mut schema {
+        schema.insert("title".into(), ty.name.into());
+
+        if !ty.comments.is_empty() {
+            schema.insert(
+                "description".into(),
+                ty.comments
+                    .iter()
+                    .map(|c| c.trim())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+                    .into(),
+            );
+        }
+    }
+
+    schema
+}
+
+fn primitive(ty: &PrimitiveType) -> Value {
+    match ty {
+        PrimitiveType::i8
+        | PrimitiveType::i16
+        | PrimitiveType::i32
+        | PrimitiveType::i64
+        | PrimitiveType::i128
+        | PrimitiveType::isize => json!({ "type": "integer" }),
+        PrimitiveType::u8
+        | PrimitiveType::u16
+        | PrimitiveType::u32
+        | PrimitiveType::u64
+        | PrimitiveType::u128
+        | PrimitiveType::usize => json!({ "type": "integer", "minimum": 0 }),
+        PrimitiveType::f32 | PrimitiveType::f64 => json!({ "type": "number" }),
+        PrimitiveType::bool => json!({ "type": "boolean" }),
+        PrimitiveType::char => json!({ "type": "string", "minLength": 1, "maxLength": 1 }),
+        PrimitiveType::String => json!({ "type": "string" }),
+    }
+}
+
+fn literal(ty: &LiteralType) -> Value {
+    match ty {
+        LiteralType::i8(v) => json!({ "const": v }),
+        LiteralType::i16(v) => json!({ "const": v }),
+        LiteralType::i32(v) => json!({ "const": v }),
+        LiteralType::u8(v) => json!({ "const": v }),
+        LiteralType::u16(v) => json!({ "const": v }),
+        LiteralType::u32(v) => json!({ "const": v }),
+        LiteralType::f32(v) => json!({ "const": v }),
+        LiteralType::f64(v) => json!({ "const": v }),
+        LiteralType::bool(v) => json!({ "const": v }),
+        LiteralType::String(v) => json!({ "const": v }),
+        LiteralType::None => json!({ "type": "null" }),
+    }
+}
+
+fn object(ty: &ObjectType, ref_prefix: &str) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut flattened = Vec::new();
+
+    for field in &ty.fields {
+        let schema = datatype(&field.ty, ref_prefix);
+
+        if field.flatten {
+            flattened.push(schema);
+            continue;
+        }
+
+        // Serde deserializes a missing `Option` as `None` so only the other fields have to be present.
+        if !field.optional && !matches!(field.ty, DataType::Nullable(_)) {
+            required.push(field.key);
+        }
+        properties.insert(field.key.to_string(), schema);
+    }
+
+    let schema = json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    });
+
+    match flattened.is_empty() {
+        true => schema,
+        false => json!({ "allOf": std::iter::once(schema).chain(flattened).collect::<Vec<_>>() }),
+    }
+}
+
+fn tuple(ty: &TupleType, ref_prefix: &str) -> Value {
+    match &ty.fields[..] {
+        // `()` and empty tuple structs are serialized as `null` by Serde
+        [] => json!({ "type": "null" }),
+        // Newtypes are serialized as their inner value
+        [ty] => datatype(ty, ref_prefix),
+        fields => json!({
+            "type": "array",
+            "prefixItems": fields.iter().map(|ty| datatype(ty, ref_prefix)).collect::<Vec<_>>(),
+            "minItems": fields.len(),
+            "maxItems": fields.len(),
+        }),
+    }
+}
+
+fn variant(variant: &EnumVariant, ref_prefix: &str) -> Option<Value> {
+    match variant {
+        EnumVariant::Unit => None,
+        EnumVariant::Unnamed(ty) => Some(tuple(ty, ref_prefix)),
+        EnumVariant::Named(ty) => Some(object(ty, ref_prefix)),
+    }
+}
+
+fn enum_type(ty: &EnumType, ref_prefix: &str) -> Value {
+    let variants = match ty {
+        EnumType::Untagged { variants, .. } => variants
+            .iter()
+            .map(|v| variant(v, ref_prefix).unwrap_or_else(|| json!({ "type": "null" })))
+            .collect::<Vec<_>>(),
+        EnumType::Tagged { variants, repr, .. } => variants
+            .iter()
+            .map(|(name, v)| match (repr, variant(v, ref_prefix)) {
+                (EnumRepr::External, None) => json!({ "const": name }),
+                (EnumRepr::External, Some(schema)) => json!({
+                    "type": "object",
+                    "properties": { *name: schema },
+                    "required": [name],
+                }),
+                (EnumRepr::Internal { tag }, schema) => {
+                    let tag = json!({
+                        "type": "object",
+                        "properties": { *tag: { "const": name } },
+                        "required": [tag],
+                    });
+
+                    match schema {
+                        Some(schema) => json!({ "allOf": [tag, schema] }),
+                        None => tag,
+                    }
+                }
+                (EnumRepr::Adjacent { tag, .. }, None) => json!({
+                    "type": "object",
+                    "properties": { *tag: { "const": name } },
+                    "required": [tag],
+                }),
+                (EnumRepr::Adjacent { tag, content }, Some(schema)) => json!({
+                    "type": "object",
+                    "properties": { *tag: { "const": name }, *content: schema },
+                    "required": [tag, content],
+                }),
+            })
+            .collect::<Vec<_>>(),
+    };
+
+    json!({ "anyOf": variants })
+}
+
+/// Validate `value` against a schema generated by this module. Only the keywords we emit are understood.
+///
+/// `refs` are the definitions already being checked against `value`. A recursive type can reference itself without descending into the value, eg. `struct Node(Option<Box<Node>>)`, so these aren't followed again to avoid recursing forever.
+fn validate(
+    schema: &Value,
+    value: &Value,
+    defs: &Map<String, Value>,
+    path: &str,
+    refs: &[&str],
+    err: &mut ValidationError,
+) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+
+    if let Some(Value::String(reference)) = schema.get("$ref") {
+        if let Some((name, def)) = reference
+            .strip_prefix(DEFS_PREFIX)
+            .and_then(|name| Some((name, defs.get(name)?)))
+        {
+            // Following the reference again would check the same value against the same schema forever
+            if refs.contains(&name) {
+                err.add(path, "references itself");
+                return;
+            }
+
+            let refs = refs.iter().copied().chain([name]).collect::<Vec<_>>();
+            validate(def, value, defs, path, &refs, err);
+        }
+    }
+
+    if let Some(expected) = schema.get("const") {
+        if value != expected {
+            err.add(path, format!("expected `{expected}`"));
+            return;
+        }
+    }
+
+    if let Some(Value::String(ty)) = schema.get("type") {
+        let matches = match ty.as_str() {
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "string" => value.is_string(),
+            "array" => value.is_array(),
+            "object" => value.is_object(),
+            _ => true,
+        };
+
+        if !matches {
+            err.add(path, format!("expected {ty}"));
+            return;
+        }
+    }
+
+    if let (Some(minimum), Some(n)) = (
+        schema.get("minimum").and_then(Value::as_f64),
+        value.as_f64(),
+    ) {
+        if n < minimum {
+            err.add(path, format!("must be greater than or equal to {minimum}"));
+        }
+    }
+
+    if let Value::String(s) = value {
+        let len = s.chars().count() as u64;
+        if matches!(schema.get("minLength").and_then(Value::as_u64), Some(min) if len < min)
+            || matches!(schema.get("maxLength").and_then(Value::as_u64), Some(max) if len > max)
+        {
+            err.add(path, "has an invalid length");
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+
+            if let Some(Value::Array(required)) = schema.get("required") {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(key) {
+                        err.add(field_path(path, key), "is required");
+                    }
+                }
+            }
+
+            for (key, value) in object {
+                match properties.and_then(|p| p.get(key)) {
+                    Some(property) => {
+                        validate(property, value, defs, &field_path(path, key), &[], err)
+                    }
+                    None => {
+                        if let Some(additional) = schema.get("additionalProperties") {
+                            validate(additional, value, defs, &field_path(path, key), &[], err);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if matches!(schema.get("minItems").and_then(Value::as_u64), Some(min) if len < min)
+                || matches!(schema.get("maxItems").and_then(Value::as_u64), Some(max) if len > max)
+            {
+                err.add(path, "has an invalid number of items");
+            }
+
+            let prefix_items = schema
+                .get("prefixItems")
+                .and_then(Value::as_array)
+                .map(|v| &v[..])
+                .unwrap_or_default();
+            for (i, value) in items.iter().enumerate() {
+                if let Some(item) = prefix_items.get(i).or_else(|| schema.get("items")) {
+                    validate(item, value, defs, &format!("{path}[{i}]"), &[], err);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(Value::Array(all_of)) = schema.get("allOf") {
+        for schema in all_of {
+            validate(schema, value, defs, path, refs, err);
+        }
+    }
+
+    // `oneOf` is treated the same as `anyOf` as we only need to know the value could be deserialized.
+    if let Some(Value::Array(any_of)) = schema.get("anyOf").or_else(|| schema.get("oneOf")) {
+        let matched = any_of.iter().any(|schema| {
+            let mut err = ValidationError::new();
+            validate(schema, value, defs, path, refs, &mut err);
+            err.is_empty()
+        });
+
+        if !matched {
+            err.add(path, "does not match any of the expected variants");
+        }
+    }
+}
+
+fn field_path(path: &str, key: &str) -> String {
+    match path.is_empty() {
+        true => key.to_string(),
+        false => format!("{path}.{key}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recursive_ref() {
+        // `struct Node(Option<Box<Node>>)` references itself without descending into the value
+        let defs = json!({
+            "Node": { "anyOf": [{ "$ref": "#/$defs/Node" }, { "type": "null" }] },
+        });
+        let schema = InputSchema {
+            schema: json!({ "$ref": "#/$defs/Node" }),
+            defs: Arc::new(defs.as_object().unwrap().clone()),
+        };
+
+        assert!(schema.check(&Value::Null).is_ok());
+        assert!(schema.check(&json!("node")).is_err());
+    }
+}
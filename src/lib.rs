@@ -19,15 +19,43 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "unstable")))]
 pub mod unstable;
 
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+mod client;
 mod compiled_router;
 mod error;
+mod json_schema;
+mod local;
+mod manifest;
+mod manifest_diff;
+mod meta;
+mod openapi;
+mod recorder;
 mod router;
 mod rspc;
+mod swappable_router;
+mod validation;
+mod version;
 
 pub use crate::rspc::*;
+pub use client::*;
 pub use compiled_router::*;
 pub use error::*;
+pub use local::*;
+pub use manifest::*;
+pub use manifest_diff::*;
+pub use meta::*;
+pub use openapi::*;
+pub use recorder::*;
 pub use router::*;
+pub use swappable_router::*;
+pub use validation::*;
+pub use version::*;
+
+pub use internal::middleware::ProcedureKind;
 
+pub mod export;
 pub mod integrations;
 pub mod internal;
@@ -0,0 +1,162 @@
+use std::{
+    cell::Cell,
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures::Stream;
+use pin_project_lite::pin_project;
+use send_wrapper::SendWrapper;
+
+use crate::internal::exec::SingleThreaded;
+
+pin_project! {
+    /// Use a `!Send` value, eg. an `Rc` or a database connection, as a context or from a resolver on a single-threaded runtime.
+    ///
+    /// rspc requires contexts to be `Send` and resolvers to return `Send` futures and streams. `Local` implements `Send` by checking it's only accessed on the thread which created it.
+    /// Accessing or dropping it from another thread will panic so it can only be created for a [`SingleThreaded`] runtime `R`.
+    /// Eg. a Tokio `current_thread` runtime or `LocalSet` with `LocalRuntime` or WebAssembly with `WasmRuntime`.
+    ///
+    /// `Local` is not `Sync` so a context holding one can only be run by an [`Executor`](crate::internal::exec::Executor) you drive from that runtime.
+    /// The webserver and Tauri integrations move requests between threads so they require `Sync` contexts.
+    ///
+    /// ```rust
+    /// use std::{cell::RefCell, rc::Rc};
+    /// use rspc::{
+    ///     internal::exec::{LocalRuntime, SingleThreaded},
+    ///     Local, Rspc,
+    /// };
+    ///
+    /// #[derive(Clone)]
+    /// struct Ctx {
+    ///     visits: Local<Rc<RefCell<u32>>, LocalRuntime>,
+    /// }
+    ///
+    /// const R: Rspc<Ctx> = Rspc::new();
+    ///
+    /// let router = R
+    ///     .router()
+    ///     .procedure(
+    ///         "visit",
+    ///         R.query(|ctx, _: ()| LocalRuntime::local(async move {
+    ///             let mut visits = ctx.visits.borrow_mut();
+    ///             *visits += 1;
+    ///             *visits
+    ///         })),
+    ///     )
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// A multi-threaded runtime would move the value to another thread so it's rejected at compile time:
+    ///
+    /// ```rust,compile_fail
+    /// use std::rc::Rc;
+    /// use rspc::{internal::exec::TokioRuntime, Local};
+    ///
+    /// let value = Local::<_, TokioRuntime>::new(Rc::new(42));
+    /// ```
+    ///
+    /// So is a context holding one which is served by an integration:
+    ///
+    #[cfg_attr(all(feature = "httpz", feature = "tokio"), doc = "```rust,compile_fail")]
+    #[cfg_attr(not(all(feature = "httpz", feature = "tokio")), doc = "```rust,ignore")]
+    /// use std::rc::Rc;
+    /// use rspc::{internal::exec::LocalRuntime, Local, Rspc};
+    ///
+    /// #[derive(Clone)]
+    /// struct Ctx {
+    ///     value: Local<Rc<u32>, LocalRuntime>,
+    /// }
+    ///
+    /// const R: Rspc<Ctx> = Rspc::new();
+    ///
+    /// let router = R.router().build().unwrap().arced();
+    /// router.endpoint(|| Ctx { value: Local::new(Rc::new(42)) });
+    /// ```
+    pub struct Local<T, R> {
+        #[pin]
+        inner: SendWrapper<T>,
+        runtime: PhantomData<fn() -> R>,
+        not_sync: PhantomData<Cell<()>>,
+    }
+}
+
+impl<T, R: SingleThreaded> Local<T, R> {
+    /// Wrap a value which can then only be accessed on the current thread.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: SendWrapper::new(value),
+            runtime: PhantomData,
+            not_sync: PhantomData,
+        }
+    }
+}
+
+impl<T, R> Local<T, R> {
+    /// Returns `true` if the value can be accessed from the current thread.
+    pub fn is_valid(&self) -> bool {
+        self.inner.valid()
+    }
+
+    /// Take the value out of the wrapper. Panics if called from another thread.
+    pub fn into_inner(self) -> T {
+        self.inner.take()
+    }
+}
+
+impl<T, R> Deref for Local<T, R> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T, R> DerefMut for Local<T, R> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+}
+
+impl<T: Clone, R: SingleThreaded> Clone for Local<T, R> {
+    fn clone(&self) -> Self {
+        Self::new((**self).clone())
+    }
+}
+
+impl<T: fmt::Debug, R> fmt::Debug for Local<T, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.is_valid() {
+            true => f.debug_tuple("Local").field(&**self).finish(),
+            false => f.debug_tuple("Local").field(&"<other thread>").finish(),
+        }
+    }
+}
+
+impl<T: Future, R> Future for Local<T, R> {
+    type Output = T::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+impl<T: Stream, R> Stream for Local<T, R> {
+    type Item = T::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.is_valid() {
+            true => (**self).size_hint(),
+            false => (0, None),
+        }
+    }
+}
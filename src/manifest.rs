@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    internal::middleware::ProcedureKind, json_schema, BuiltRouter, ExportError, ProcedureMeta,
+};
+
+/// `$ref`s in a manifest point to an entry of [`Manifest::types`] by name. Eg. `#/types/User`.
+pub const MANIFEST_REF_PREFIX: &str = "#/types/";
+
+/// A machine-readable description of every procedure and type of a router.
+///
+/// The manifest is deterministic so it can be committed and compared between versions of an API. Types are described using JSON Schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the manifest format. Bumped when the format changes in an incompatible way.
+    pub version: u32,
+    /// Every procedure ordered by kind and then key.
+    pub procedures: Vec<ManifestProcedure>,
+    /// Every named type used by the router by name.
+    pub types: BTreeMap<String, ManifestType>,
+}
+
+impl Manifest {
+    pub const VERSION: u32 = 1;
+
+    /// Find a procedure in the manifest.
+    pub fn procedure(&self, kind: ProcedureKind, key: &str) -> Option<&ManifestProcedure> {
+        self.procedures
+            .iter()
+            .find(|p| p.kind == kind && p.key == key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestProcedure {
+    pub key: String,
+    pub kind: ProcedureKind,
+    /// The JSON Schema of the input. Named types are referenced using [`MANIFEST_REF_PREFIX`].
+    pub input: Value,
+    /// The JSON Schema of the result. Named types are referenced using [`MANIFEST_REF_PREFIX`].
+    pub result: Value,
+    #[serde(default, skip_serializing_if = "ProcedureMeta::is_empty")]
+    pub meta: ProcedureMeta,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestType {
+    /// A hash of the schema which only changes when the shape of the type changes.
+    ///
+    /// Types referenced by this type are hashed by name so a change to them does not change this hash.
+    pub hash: String,
+    pub schema: Value,
+}
+
+impl<TCtx> BuiltRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    /// Generate a [`Manifest`] describing the procedures and types of this router.
+    pub fn manifest(&self) -> Manifest {
+        Manifest {
+            version: Manifest::VERSION,
+            procedures: self
+                .procedures()
+                .map(|p| ManifestProcedure {
+                    key: p.key.to_string(),
+                    kind: p.kind,
+                    input: json_schema::datatype(p.input, MANIFEST_REF_PREFIX),
+                    result: json_schema::datatype(p.result, MANIFEST_REF_PREFIX),
+                    meta: p.meta.clone(),
+                })
+                .collect(),
+            types: json_schema::definitions(&self.typ_store, MANIFEST_REF_PREFIX)
+                .into_iter()
+                .map(|(name, schema)| {
+                    (
+                        name,
+                        ManifestType {
+                            hash: hash(&schema),
+                            schema,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Write the manifest generated by [`BuiltRouter::manifest`] to a file.
+    pub fn export_manifest(&self, export_path: impl Into<PathBuf>) -> Result<(), ExportError> {
+        let export_path = export_path.into();
+        if let Some(export_dir) = export_path.parent() {
+            fs::create_dir_all(export_dir)?;
+        }
+
+        let file = fs::File::create(export_path)?;
+        serde_json::to_writer_pretty(file, &self.manifest()).map_err(io::Error::from)?;
+        Ok(())
+    }
+}
+
+/// A 64-bit FNV-1a hash of a JSON value. Object keys are hashed in sorted order so the hash doesn't depend on how the map is ordered.
+///
+/// We don't use `std::hash` as its output is not guaranteed to be stable between Rust releases.
+fn hash(value: &Value) -> String {
+    struct Fnv(u64);
+
+    impl Fnv {
+        fn write(&mut self, bytes: &[u8]) {
+            for byte in bytes {
+                self.0 ^= *byte as u64;
+                self.0 = self.0.wrapping_mul(0x100000001b3);
+            }
+        }
+
+        fn value(&mut self, value: &Value) {
+            match value {
+                Value::Null => self.write(b"n"),
+                Value::Bool(v) => self.write(if *v { b"t" } else { b"f" }),
+                Value::Number(v) => {
+                    self.write(b"#");
+                    self.write(v.to_string().as_bytes());
+                }
+                Value::String(v) => {
+                    self.write(b"\"");
+                    self.write(&(v.len() as u64).to_le_bytes());
+                    self.write(v.as_bytes());
+                }
+                Value::Array(v) => {
+                    self.write(b"[");
+                    self.write(&(v.len() as u64).to_le_bytes());
+                    for v in v {
+                        self.value(v);
+                    }
+                }
+                Value::Object(v) => {
+                    let mut entries = v.iter().collect::<Vec<_>>();
+                    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+                    self.write(b"{");
+                    self.write(&(entries.len() as u64).to_le_bytes());
+                    for (key, v) in entries {
+                        self.write(&(key.len() as u64).to_le_bytes());
+                        self.write(key.as_bytes());
+                        self.value(v);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut hasher = Fnv(0xcbf29ce484222325);
+    hasher.value(value);
+    format!("{:016x}", hasher.0)
+}
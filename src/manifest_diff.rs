@@ -0,0 +1,544 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use serde_json::{Map, Value};
+
+use crate::{internal::middleware::ProcedureKind, Manifest, ManifestType, MANIFEST_REF_PREFIX};
+
+/// The changes between two versions of a [`Manifest`].
+///
+/// A change is breaking when a client built against the old manifest may fail against a server using the new manifest.
+/// Inputs must accept everything they accepted before and results must not return anything they couldn't return before.
+///
+/// ```rust
+/// use rspc::Rspc;
+///
+/// const R: Rspc<()> = Rspc::new();
+///
+/// let old = R.router().procedure("version", R.query(|_, _: ()| "1.0.0")).build().unwrap();
+/// let new = R.router().build().unwrap();
+///
+/// let diff = old.manifest().diff(&new.manifest());
+/// assert!(diff.is_breaking());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ManifestDiff {
+    pub changes: Vec<ManifestChange>,
+}
+
+impl ManifestDiff {
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|c| c.breaking)
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &ManifestChange> + '_ {
+        self.changes.iter().filter(|c| c.breaking)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// A single change to a procedure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManifestChange {
+    pub kind: ProcedureKind,
+    pub key: String,
+    /// Where in the procedure the change happened. Eg. `input.user.name` or `result[0]`. Empty for changes to the procedure itself.
+    pub path: String,
+    pub message: String,
+    pub breaking: bool,
+}
+
+impl fmt::Display for ManifestChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} `{}`",
+            match self.breaking {
+                true => "[breaking]",
+                false => "[non-breaking]",
+            },
+            self.kind.to_str(),
+            self.key
+        )?;
+        if !self.path.is_empty() {
+            write!(f, " {}", self.path)?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl Manifest {
+    /// Compare this manifest with a newer version of it.
+    pub fn diff(&self, new: &Manifest) -> ManifestDiff {
+        let mut diff = ManifestDiff::default();
+
+        for old_p in &self.procedures {
+            let change = |path: &str, message: String, breaking| ManifestChange {
+                kind: old_p.kind,
+                key: old_p.key.clone(),
+                path: path.into(),
+                message,
+                breaking,
+            };
+
+            if let Some(new_p) = new.procedure(old_p.kind, &old_p.key) {
+                let mut cmp = Compare {
+                    old_types: &self.types,
+                    new_types: &new.types,
+                    visited: BTreeSet::new(),
+                    changes: Vec::new(),
+                };
+                cmp.schema(&old_p.input, &new_p.input, Direction::Input, "input");
+                cmp.schema(&old_p.result, &new_p.result, Direction::Result, "result");
+                diff.changes.extend(
+                    cmp.changes
+                        .into_iter()
+                        .map(|(path, message, breaking)| change(&path, message, breaking)),
+                );
+                continue;
+            }
+
+            let moved = new
+                .procedures
+                .iter()
+                .find(|p| p.key == old_p.key && self.procedure(p.kind, &p.key).is_none());
+            diff.changes.push(match moved {
+                Some(new_p) => change(
+                    "",
+                    format!(
+                        "changed from a {} to a {}",
+                        old_p.kind.to_str(),
+                        new_p.kind.to_str()
+                    ),
+                    true,
+                ),
+                None => change("", "removed".into(), true),
+            });
+        }
+
+        for new_p in &new.procedures {
+            let exists = self.procedure(new_p.kind, &new_p.key).is_some()
+                || self
+                    .procedures
+                    .iter()
+                    .any(|p| p.key == new_p.key && new.procedure(p.kind, &p.key).is_none());
+
+            if !exists {
+                diff.changes.push(ManifestChange {
+                    kind: new_p.kind,
+                    key: new_p.key.clone(),
+                    path: String::new(),
+                    message: "added".into(),
+                    breaking: false,
+                });
+            }
+        }
+
+        diff
+    }
+}
+
+/// Which way the data flows. Inputs are sent by the client so may accept more, results are received by the client so may return less.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Input,
+    Result,
+}
+
+struct Compare<'a> {
+    old_types: &'a BTreeMap<String, ManifestType>,
+    new_types: &'a BTreeMap<String, ManifestType>,
+    // The pairs of types which were already compared so recursive types terminate and changes to a shared type are only reported once.
+    visited: BTreeSet<(String, String, bool)>,
+    changes: Vec<(String, String, bool)>,
+}
+
+impl<'a> Compare<'a> {
+    /// The new schema accepts values the old one didn't.
+    fn widened(&mut self, dir: Direction, path: &str, message: impl Into<String>) {
+        self.changes
+            .push((path.into(), message.into(), dir == Direction::Result));
+    }
+
+    /// The new schema doesn't accept values the old one did.
+    fn narrowed(&mut self, dir: Direction, path: &str, message: impl Into<String>) {
+        self.changes
+            .push((path.into(), message.into(), dir == Direction::Input));
+    }
+
+    fn changed(&mut self, path: &str, message: impl Into<String>) {
+        self.changes.push((path.into(), message.into(), true));
+    }
+
+    fn schema(&mut self, old: &'a Value, new: &'a Value, dir: Direction, path: &str) {
+        if let (Some(old_name), Some(new_name)) = (ref_name(old), ref_name(new)) {
+            if !self.visited.insert((
+                old_name.to_string(),
+                new_name.to_string(),
+                dir == Direction::Input,
+            )) {
+                return;
+            }
+        }
+
+        let old_alts = alternatives(old, self.old_types, &mut Vec::new());
+        let new_alts = alternatives(new, self.new_types, &mut Vec::new());
+        if let ([old], [new]) = (&old_alts[..], &new_alts[..]) {
+            return self.single(old, new, dir, path);
+        }
+
+        // A plain type which was made nullable is compared directly.
+        let old_non_null = old_alts.iter().filter(|s| !is_null(s)).collect::<Vec<_>>();
+        let new_non_null = new_alts.iter().filter(|s| !is_null(s)).collect::<Vec<_>>();
+        if let ([old_inner], [new_inner]) = (&old_non_null[..], &new_non_null[..]) {
+            match (old_alts.len() > 1, new_alts.len() > 1) {
+                (false, true) => self.widened(dir, path, "is now nullable"),
+                (true, false) => self.narrowed(dir, path, "is no longer nullable"),
+                _ => {}
+            }
+
+            return self.single(old_inner, new_inner, dir, path);
+        }
+
+        let old_keyed = old_alts
+            .iter()
+            .map(|s| (variant_key(s), *s))
+            .collect::<BTreeMap<_, _>>();
+        let new_keyed = new_alts
+            .iter()
+            .map(|s| (variant_key(s), *s))
+            .collect::<BTreeMap<_, _>>();
+
+        for (key, old) in &old_keyed {
+            match new_keyed.get(key) {
+                Some(new) => self.single(old, new, dir, path),
+                None => self.narrowed(dir, path, format!("variant `{key}` was removed")),
+            }
+        }
+        for key in new_keyed.keys() {
+            if !old_keyed.contains_key(key) {
+                self.widened(dir, path, format!("variant `{key}` was added"));
+            }
+        }
+    }
+
+    /// Compare two schemas which aren't unions.
+    fn single(&mut self, old: &'a Value, new: &'a Value, dir: Direction, path: &str) {
+        let old = resolve(old, self.old_types);
+        let new = resolve(new, self.new_types);
+
+        // A newtype of another named type
+        if ref_name(old).is_some() || ref_name(new).is_some() {
+            return self.schema(old, new, dir, path);
+        }
+
+        if strip(old) == strip(new) && ref_free(old) && ref_free(new) {
+            return;
+        }
+
+        if let (Some(old), Some(new)) = (
+            object(old, self.old_types, &mut Vec::new()),
+            object(new, self.new_types, &mut Vec::new()),
+        ) {
+            return self.object(old, new, dir, path);
+        }
+
+        match (get(old, "const"), get(new, "const")) {
+            (Some(old), Some(new)) if old == new => return,
+            (Some(old), Some(new)) => {
+                return self.changed(path, format!("changed from `{old}` to `{new}`"))
+            }
+            (Some(_), None) if !is_any(new) && type_of(old) == type_of(new) => {
+                return self.widened(dir, path, "is no longer a constant")
+            }
+            (None, Some(_)) if !is_any(old) && type_of(old) == type_of(new) => {
+                return self.narrowed(dir, path, "is now a constant")
+            }
+            _ => {}
+        }
+
+        match (is_any(old), is_any(new)) {
+            (true, true) => return,
+            (false, true) => return self.widened(dir, path, "now accepts any value"),
+            (true, false) => return self.narrowed(dir, path, "no longer accepts any value"),
+            _ => {}
+        }
+
+        let (old_ty, new_ty) = (type_of(old), type_of(new));
+        if old_ty != new_ty {
+            return match (old_ty, new_ty) {
+                (Some("integer"), Some("number")) => {
+                    self.widened(dir, path, "changed from `integer` to `number`")
+                }
+                (Some("number"), Some("integer")) => {
+                    self.narrowed(dir, path, "changed from `number` to `integer`")
+                }
+                (old, new) => self.changed(
+                    path,
+                    format!(
+                        "changed from `{}` to `{}`",
+                        old.unwrap_or("unknown"),
+                        new.unwrap_or("unknown")
+                    ),
+                ),
+            };
+        }
+
+        for keyword in ["minimum", "minLength"] {
+            match (get(old, keyword), get(new, keyword)) {
+                (Some(_), None) => self.widened(dir, path, format!("`{keyword}` was removed")),
+                (None, Some(_)) => self.narrowed(dir, path, format!("`{keyword}` was added")),
+                (Some(a), Some(b)) if a != b => self.changed(path, format!("`{keyword}` changed")),
+                _ => {}
+            }
+        }
+        if let (Some(a), Some(b)) = (get(old, "maxLength"), get(new, "maxLength")) {
+            if a != b {
+                self.changed(path, "`maxLength` changed");
+            }
+        }
+
+        if let (Some(old), Some(new)) = (get(old, "items"), get(new, "items")) {
+            self.schema(old, new, dir, &format!("{path}[]"));
+        }
+
+        if let (Some(Value::Array(old)), Some(Value::Array(new))) =
+            (get(old, "prefixItems"), get(new, "prefixItems"))
+        {
+            if old.len() != new.len() {
+                return self.changed(
+                    path,
+                    format!("changed from {} to {} elements", old.len(), new.len()),
+                );
+            }
+            for (i, (old, new)) in old.iter().zip(new).enumerate() {
+                self.schema(old, new, dir, &format!("{path}[{i}]"));
+            }
+        }
+
+        if let (Some(old), Some(new)) = (
+            get(old, "additionalProperties"),
+            get(new, "additionalProperties"),
+        ) {
+            self.schema(old, new, dir, &format!("{path}{{}}"));
+        }
+    }
+
+    fn object(&mut self, old: Object<'a>, new: Object<'a>, dir: Direction, path: &str) {
+        for (key, old_schema) in &old.properties {
+            let field_path = format!("{path}.{key}");
+            let old_required = old.required.contains(key);
+
+            let Some(new_schema) = new.properties.get(key) else {
+                match (dir, old_required) {
+                    // Serde ignores unknown fields by default so old clients can continue to send it.
+                    (Direction::Input, _) => self.widened(dir, &field_path, "field was removed"),
+                    (Direction::Result, true) => {
+                        self.changed(&field_path, "required field was removed")
+                    }
+                    (Direction::Result, false) => {
+                        self.narrowed(dir, &field_path, "optional field was removed")
+                    }
+                }
+                continue;
+            };
+
+            match (old_required, new.required.contains(key)) {
+                (true, false) => self.widened(dir, &field_path, "field is now optional"),
+                (false, true) => self.narrowed(dir, &field_path, "field is now required"),
+                _ => {}
+            }
+            self.schema(old_schema, new_schema, dir, &field_path);
+        }
+
+        for key in new.properties.keys() {
+            if old.properties.contains_key(key) {
+                continue;
+            }
+
+            let field_path = format!("{path}.{key}");
+            match new.required.contains(key) {
+                true => self.narrowed(dir, &field_path, "required field was added"),
+                false => self
+                    .changes
+                    .push((field_path, "optional field was added".into(), false)),
+            }
+        }
+    }
+}
+
+/// The properties of an object schema with any flattened objects merged in.
+struct Object<'a> {
+    properties: BTreeMap<&'a str, &'a Value>,
+    required: BTreeSet<&'a str>,
+}
+
+fn object<'a>(
+    schema: &'a Value,
+    types: &'a BTreeMap<String, ManifestType>,
+    seen: &mut Vec<&'a str>,
+) -> Option<Object<'a>> {
+    if let Some(name) = ref_name(schema) {
+        if seen.contains(&name) {
+            return None;
+        }
+        seen.push(name);
+        return object(resolve(schema, types), types, seen);
+    }
+
+    if let Some(Value::Array(all)) = get(schema, "allOf") {
+        let mut result = Object {
+            properties: BTreeMap::new(),
+            required: BTreeSet::new(),
+        };
+        for schema in all {
+            let obj = object(schema, types, seen)?;
+            result.properties.extend(obj.properties);
+            result.required.extend(obj.required);
+        }
+        return Some(result);
+    }
+
+    if type_of(schema) != Some("object") || get(schema, "additionalProperties").is_some() {
+        return None;
+    }
+
+    Some(Object {
+        properties: match get(schema, "properties") {
+            Some(Value::Object(properties)) => {
+                properties.iter().map(|(k, v)| (k.as_str(), v)).collect()
+            }
+            _ => BTreeMap::new(),
+        },
+        required: match get(schema, "required") {
+            Some(Value::Array(required)) => required.iter().filter_map(Value::as_str).collect(),
+            _ => BTreeSet::new(),
+        },
+    })
+}
+
+/// The alternatives of a union. A schema which isn't a union is a union of itself.
+fn alternatives<'a>(
+    schema: &'a Value,
+    types: &'a BTreeMap<String, ManifestType>,
+    seen: &mut Vec<&'a str>,
+) -> Vec<&'a Value> {
+    if let Some(name) = ref_name(schema) {
+        if seen.contains(&name) {
+            return vec![schema];
+        }
+        seen.push(name);
+    }
+
+    match get(resolve(schema, types), "anyOf") {
+        Some(Value::Array(alts)) => alts
+            .iter()
+            .flat_map(|alt| alternatives(alt, types, seen))
+            .collect(),
+        _ => vec![schema],
+    }
+}
+
+/// A name which identifies an alternative of a union across versions. Eg. the name of an enum variant.
+fn variant_key(schema: &Value) -> String {
+    if let Some(name) = ref_name(schema) {
+        return name.to_string();
+    }
+    if let Some(value) = get(schema, "const") {
+        return value.to_string();
+    }
+    if let Some(Value::Array(all)) = get(schema, "allOf") {
+        if let Some(first) = all.first() {
+            return variant_key(first);
+        }
+    }
+
+    if let Some(Value::Object(properties)) = get(schema, "properties") {
+        // Internally and adjacently tagged variants
+        for (key, value) in properties {
+            if let Some(tag) = get(value, "const") {
+                return format!("{key}={tag}");
+            }
+        }
+
+        // Externally tagged variants
+        if properties.len() == 1 {
+            if let Some(key) = properties.keys().next() {
+                return key.clone();
+            }
+        }
+    }
+
+    match type_of(schema) {
+        Some(ty) => ty.to_string(),
+        None => serde_json::to_string(&strip(schema)).unwrap_or_default(),
+    }
+}
+
+fn ref_name(schema: &Value) -> Option<&str> {
+    get(schema, "$ref")?
+        .as_str()?
+        .strip_prefix(MANIFEST_REF_PREFIX)
+}
+
+fn resolve<'a>(schema: &'a Value, types: &'a BTreeMap<String, ManifestType>) -> &'a Value {
+    match ref_name(schema).and_then(|name| types.get(name)) {
+        Some(ty) => &ty.schema,
+        None => schema,
+    }
+}
+
+/// Returns `true` if the schema contains a `$ref` so comparing it by value isn't enough.
+fn ref_free(schema: &Value) -> bool {
+    match schema {
+        Value::Object(map) => !map.contains_key("$ref") && map.values().all(ref_free),
+        Value::Array(items) => items.iter().all(ref_free),
+        _ => true,
+    }
+}
+
+/// Remove annotations which don't affect which values are valid.
+fn strip(schema: &Value) -> Value {
+    match schema {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(k, _)| !matches!(k.as_str(), "title" | "description"))
+                .map(|(k, v)| (k.clone(), strip(v)))
+                .collect::<Map<_, _>>(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(strip).collect()),
+        v => v.clone(),
+    }
+}
+
+fn get<'a>(schema: &'a Value, keyword: &str) -> Option<&'a Value> {
+    schema.as_object()?.get(keyword)
+}
+
+fn type_of(schema: &Value) -> Option<&str> {
+    match get(schema, "type") {
+        Some(ty) => ty.as_str(),
+        None => match get(schema, "const")? {
+            Value::Null => Some("null"),
+            Value::Bool(_) => Some("boolean"),
+            Value::Number(n) if n.is_f64() => Some("number"),
+            Value::Number(_) => Some("integer"),
+            Value::String(_) => Some("string"),
+            Value::Array(_) => Some("array"),
+            Value::Object(_) => Some("object"),
+        },
+    }
+}
+
+fn is_null(schema: &Value) -> bool {
+    type_of(schema) == Some("null") && get(schema, "const").is_none()
+}
+
+fn is_any(schema: &Value) -> bool {
+    strip(schema).as_object().is_some_and(Map::is_empty)
+}
@@ -0,0 +1,63 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Metadata attached to a procedure when it is defined.
+///
+/// It is exposed to middleware through [`RequestContext::meta`](crate::internal::middleware::RequestContext::meta), can be queried from the [`BuiltRouter`](crate::BuiltRouter) and is exported as JSDoc comments in the Typescript bindings.
+///
+/// ```rust
+/// use rspc::Rspc;
+///
+/// const R: Rspc<()> = Rspc::new();
+///
+/// let procedure = R
+///     .query(|_, _: ()| "1.0.0")
+///     .description("Get the version of the server")
+///     .tag("meta")
+///     .deprecated("Use `info` instead");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProcedureMeta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) description: Option<Cow<'static, str>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<Cow<'static, str>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) deprecated: Option<Cow<'static, str>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) extra: BTreeMap<Cow<'static, str>, Value>,
+}
+
+impl ProcedureMeta {
+    /// A description of what the procedure does.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The tags used to group the procedure.
+    pub fn tags(&self) -> impl Iterator<Item = &str> + '_ {
+        self.tags.iter().map(|t| t.as_ref())
+    }
+
+    /// The reason the procedure is deprecated. `None` means it is not deprecated.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Get an arbitrary value which was attached to the procedure.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.extra.get(key)
+    }
+
+    /// All the arbitrary values which were attached to the procedure.
+    pub fn extra(&self) -> &BTreeMap<Cow<'static, str>, Value> {
+        &self.extra
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
@@ -0,0 +1,306 @@
+use std::{borrow::Cow, fs, io, path::PathBuf};
+
+use serde_json::{json, Map, Value};
+use specta::DataType;
+
+use crate::{
+    internal::{middleware::ProcedureKind, ProcedureTodo},
+    json_schema, BuiltRouter, ExportError,
+};
+
+const REF_PREFIX: &str = "#/components/schemas/";
+
+/// Swagger UI is pinned to an exact version so the page doesn't change underneath you.
+const SWAGGER_UI_URL: &str = "https://unpkg.com/swagger-ui-dist@5.9.0";
+
+/// OpenApiConfig is used to configure the OpenAPI document generated by [`BuiltRouter::openapi`].
+///
+/// ```rust
+/// use rspc::OpenApiConfig;
+///
+/// let cfg = OpenApiConfig::new("My API", "1.0.0")
+///     .description("The API behind my app")
+///     .server("https://api.example.com/rspc");
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiConfig {
+    title: Cow<'static, str>,
+    version: Cow<'static, str>,
+    description: Option<Cow<'static, str>>,
+    servers: Vec<Cow<'static, str>>,
+    // Only the httpz integration serves the page which uses this.
+    #[cfg_attr(not(feature = "httpz"), allow(dead_code))]
+    swagger_ui_url: Cow<'static, str>,
+}
+
+impl OpenApiConfig {
+    pub fn new(title: impl Into<Cow<'static, str>>, version: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            description: None,
+            servers: Vec::new(),
+            swagger_ui_url: Cow::Borrowed(SWAGGER_UI_URL),
+        }
+    }
+
+    pub fn description(self, description: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add the URL the rspc endpoint is mounted at. Eg. `https://example.com/rspc`.
+    pub fn server(mut self, url: impl Into<Cow<'static, str>>) -> Self {
+        self.servers.push(url.into());
+        self
+    }
+
+    /// The URL the page served at `rspc.docs` loads Swagger UI from. It must contain the `swagger-ui.css` and `swagger-ui-bundle.js` files of the [`swagger-ui-dist`](https://www.npmjs.com/package/swagger-ui-dist) package.
+    ///
+    /// The page runs on the same origin as your API so you may want to host these files yourself. By default they are loaded from unpkg.
+    pub fn swagger_ui_url(self, url: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            swagger_ui_url: url.into(),
+            ..self
+        }
+    }
+
+    /// A page which renders the OpenAPI document served next to it using Swagger UI.
+    #[cfg(feature = "httpz")]
+    pub(crate) fn ui(&self) -> String {
+        let url = self
+            .swagger_ui_url
+            .trim_end_matches('/')
+            .replace('&', "&amp;")
+            .replace('"', "&quot;")
+            .replace('<', "&lt;");
+
+        format!(
+            r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1" />
+    <title>rspc</title>
+    <link rel="stylesheet" href="{url}/swagger-ui.css" />
+</head>
+<body>
+    <div id="swagger-ui"></div>
+    <script src="{url}/swagger-ui-bundle.js" crossorigin></script>
+    <script>
+        window.onload = () => {{
+            window.ui = SwaggerUIBundle({{ url: "rspc.openapi.json", dom_id: "#swagger-ui" }});
+        }};
+    </script>
+</body>
+</html>
+"##
+        )
+    }
+}
+
+impl<TCtx> BuiltRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    /// Generate an [OpenAPI 3.1](https://spec.openapis.org/oas/v3.1.0) document describing the HTTP API of this router.
+    ///
+    /// Queries are `GET` requests with the JSON encoded input in the `input` query parameter and mutations are `POST` requests with the input as the body.
+    /// Subscriptions can't be represented in OpenAPI so they are described using the `x-rspc-subscription` extension on their path.
+    pub fn openapi(&self, cfg: &OpenApiConfig) -> Value {
+        let mut paths = Map::new();
+        for (kind, store) in [
+            (ProcedureKind::Query, &self.queries),
+            (ProcedureKind::Mutation, &self.mutations),
+            (ProcedureKind::Subscription, &self.subscriptions),
+        ] {
+            for (key, procedure) in &store.store {
+                let path_item = paths.entry(format!("/{key}")).or_insert_with(|| json!({}));
+
+                if let Value::Object(path_item) = path_item {
+                    let (field, operation) = operation(kind, key, procedure);
+                    path_item.insert(field.into(), operation);
+                }
+            }
+        }
+
+        let mut schemas = json_schema::definitions(&self.typ_store, REF_PREFIX);
+        schemas.insert(
+            "RspcError".into(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "code": { "type": "integer", "minimum": 0 },
+                    "message": { "type": "string" },
+                    "data": {},
+                },
+                "required": ["code", "message"],
+            }),
+        );
+
+        let mut info = json!({
+            "title": cfg.title,
+            "version": cfg.version,
+        });
+        if let (Some(description), Value::Object(info)) = (&cfg.description, &mut info) {
+            info.insert("description".into(), description.clone().into());
+        }
+
+        json!({
+            "openapi": "3.1.0",
+            "info": info,
+            "servers": cfg.servers.iter().map(|url| json!({ "url": url })).collect::<Vec<_>>(),
+            "paths": paths,
+            "components": {
+                "schemas": schemas,
+            },
+        })
+    }
+
+    /// Write the document generated by [`BuiltRouter::openapi`] to a file.
+    pub fn export_openapi(
+        &self,
+        export_path: impl Into<PathBuf>,
+        cfg: &OpenApiConfig,
+    ) -> Result<(), ExportError> {
+        let export_path = export_path.into();
+        if let Some(export_dir) = export_path.parent() {
+            fs::create_dir_all(export_dir)?;
+        }
+
+        let file = fs::File::create(export_path)?;
+        serde_json::to_writer_pretty(file, &self.openapi(cfg)).map_err(io::Error::from)?;
+        Ok(())
+    }
+}
+
+/// Returns the field of the path item the procedure belongs in and the operation object.
+fn operation<TCtx>(
+    kind: ProcedureKind,
+    key: &str,
+    procedure: &ProcedureTodo<TCtx>,
+) -> (&'static str, Value) {
+    let input = match &procedure.ty.input {
+        // This condition is met with an empty enum or `()`. The procedure takes no input.
+        DataType::Tuple(def) if def.fields.is_empty() => None,
+        ty => Some(json_schema::datatype(ty, REF_PREFIX)),
+    };
+    let result = json_schema::datatype(&procedure.ty.result, REF_PREFIX);
+
+    let (field, mut operation) = match kind {
+        ProcedureKind::Query => (
+            "get",
+            json!({
+                "operationId": format!("query.{key}"),
+                "parameters": input.map(|schema| vec![json!({
+                    "name": "input",
+                    "in": "query",
+                    "required": true,
+                    "content": { "application/json": { "schema": schema } },
+                })]).unwrap_or_default(),
+                "responses": responses(result),
+            }),
+        ),
+        ProcedureKind::Mutation => {
+            let mut operation = json!({
+                "operationId": format!("mutation.{key}"),
+                "responses": responses(result),
+            });
+            if let (Some(schema), Value::Object(operation)) = (input, &mut operation) {
+                operation.insert(
+                    "requestBody".into(),
+                    json!({
+                        "required": true,
+                        "content": { "application/json": { "schema": schema } },
+                    }),
+                );
+            }
+
+            ("post", operation)
+        }
+        ProcedureKind::Subscription => (
+            "x-rspc-subscription",
+            json!({
+                "operationId": format!("subscription.{key}"),
+                "endpoint": "/ws",
+                "protocol": "websocket",
+                "input": input.unwrap_or_else(|| json!({ "type": "null" })),
+                "result": result,
+            }),
+        ),
+    };
+
+    if let Value::Object(operation) = &mut operation {
+        let meta = &procedure.meta;
+        if let Some(description) = meta.description() {
+            operation.insert("description".into(), description.into());
+        }
+        if meta.tags().next().is_some() {
+            operation.insert("tags".into(), meta.tags().collect::<Vec<_>>().into());
+        }
+        if meta.deprecated().is_some() {
+            operation.insert("deprecated".into(), true.into());
+        }
+        if !meta.extra().is_empty() {
+            operation.insert("x-rspc-meta".into(), json!(meta.extra()));
+        }
+    }
+
+    (field, operation)
+}
+
+/// Procedures always respond with `200 OK` and wrap the result so the client can tell errors apart.
+///
+/// Requests which the endpoint rejects before they are executed respond with the status code of the error instead.
+fn responses(result: Value) -> Value {
+    let error = |description: &str| {
+        json!({
+            "description": description,
+            "content": {
+                "application/json": {
+                    "schema": error_schema(),
+                },
+            },
+        })
+    };
+
+    json!({
+        "200": {
+            "description": "The result of the procedure",
+            "content": {
+                "application/json": {
+                    "schema": {
+                        "oneOf": [
+                            {
+                                "type": "object",
+                                "properties": {
+                                    "type": { "const": "value" },
+                                    "value": result,
+                                },
+                                "required": ["type", "value"],
+                            },
+                            error_schema(),
+                        ],
+                    },
+                },
+            },
+        },
+        "400": error("The input or the requested `version` of the request is invalid"),
+        "403": error("The request failed the CSRF check of the endpoint"),
+        "404": error("No version of the procedure matches the requested `version`"),
+        "413": error("The request exceeded the size limits of the endpoint"),
+    })
+}
+
+fn error_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "type": { "const": "error" },
+            "value": { "$ref": format!("{REF_PREFIX}RspcError") },
+        },
+        "required": ["type", "value"],
+    })
+}
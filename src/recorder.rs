@@ -0,0 +1,426 @@
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc, Mutex, PoisonError,
+    },
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::internal::exec::{Request, Response};
+
+/// A single request or response written by a [`Recorder`]. Each record is a line of the recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// When the message was recorded in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    /// The connection the message was sent over, eg. a websocket or Tauri window. Every HTTP request is its own connection.
+    pub connection: u64,
+    #[serde(flatten)]
+    pub message: RecordedMessage,
+}
+
+/// The message of a [`Record`] in the same JSON format it's sent over the wire.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecordedMessage {
+    Request(Value),
+    Response(Value),
+}
+
+type RedactFn = Arc<dyn Fn(&mut Record) + Send + Sync>;
+
+/// Records every request and response of an [`Executor`](crate::internal::exec::Executor) as JSON lines so a user's traffic can be inspected and replayed with [`replay`].
+///
+/// Attach it using `Executor::with_recorder`, `EndpointConfig::recorder` or `integrations::tauri::plugin_with_recorder`.
+///
+/// ```rust,no_run
+/// use rspc::Recorder;
+///
+/// let recorder = Recorder::file("./rspc.jsonl")
+///     .unwrap()
+///     .redact(|record| {
+///         // Don't write passwords to disk
+///         if let Some(input) = record.request_mut().and_then(|req| req.get_mut("input")) {
+///             if let Some(password) = input.get_mut("password") {
+///                 *password = "<redacted>".into();
+///             }
+///         }
+///     });
+/// ```
+#[derive(Clone)]
+pub struct Recorder {
+    sink: Sink,
+    redact: Option<RedactFn>,
+    next_connection: Arc<AtomicU64>,
+}
+
+/// The most records written before the writer is flushed.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+const MAX_BATCH: usize = 1024;
+
+enum Message {
+    Record(Record),
+    Flush(mpsc::SyncSender<()>),
+}
+
+#[derive(Clone)]
+enum Sink {
+    /// Records are sent to a background thread which writes them and flushes the writer once per batch so the executor never blocks on IO.
+    Thread(mpsc::Sender<Message>),
+    /// Used when threads aren't available, eg. on `wasm32-unknown-unknown`.
+    Inline(Arc<Mutex<Box<dyn Write + Send>>>),
+}
+
+impl Recorder {
+    /// Write the recording to `writer`.
+    ///
+    /// Records are written on a background thread and the writer is flushed whenever there are no more records queued, so it doesn't fall far behind if the app crashes.
+    /// Use [`Recorder::flush`] to wait for everything recorded so far to be written.
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        Self {
+            sink: Sink::new(Box::new(writer)),
+            redact: None,
+            next_connection: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Append the recording to the file at `path`, creating it if it doesn't exist.
+    pub fn file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::new(BufWriter::new(file)))
+    }
+
+    /// Modify every record before it's written. Eg. to remove secrets from the inputs and results of procedures.
+    ///
+    /// Multiple functions are run in the order they were added.
+    pub fn redact(self, redact: impl Fn(&mut Record) + Send + Sync + 'static) -> Self {
+        let redact: RedactFn = match self.redact {
+            Some(prev) => Arc::new(move |record| {
+                prev(record);
+                redact(record);
+            }),
+            None => Arc::new(redact),
+        };
+
+        Self {
+            redact: Some(redact),
+            ..self
+        }
+    }
+
+    /// Assign an id to a new connection.
+    pub(crate) fn connection(&self) -> u64 {
+        self.next_connection.fetch_add(1, Ordering::Relaxed)
+    }
+
+    pub(crate) fn request(&self, connection: u64, req: &Request) {
+        if let Ok(req) = serde_json::to_value(req) {
+            self.write(connection, RecordedMessage::Request(req));
+        }
+    }
+
+    pub(crate) fn response(&self, connection: u64, resp: &Response) {
+        if let Ok(resp) = serde_json::to_value(resp) {
+            self.write(connection, RecordedMessage::Response(resp));
+        }
+    }
+
+    /// Block until every record recorded so far has been written and the writer flushed.
+    pub fn flush(&self) {
+        match &self.sink {
+            Sink::Thread(tx) => {
+                let (done_tx, done_rx) = mpsc::sync_channel(1);
+                if tx.send(Message::Flush(done_tx)).is_ok() {
+                    done_rx.recv().ok();
+                }
+            }
+            Sink::Inline(writer) => log_err(
+                writer
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .flush(),
+            ),
+        }
+    }
+
+    fn write(&self, connection: u64, message: RecordedMessage) {
+        let mut record = Record {
+            timestamp: now_millis(),
+            connection,
+            message,
+        };
+        if let Some(redact) = &self.redact {
+            redact(&mut record);
+        }
+
+        match &self.sink {
+            // The thread only exits once every sender is dropped so this can't fail
+            Sink::Thread(tx) => {
+                tx.send(Message::Record(record)).ok();
+            }
+            Sink::Inline(writer) => {
+                let mut writer = writer.lock().unwrap_or_else(PoisonError::into_inner);
+                log_err(write_record(&mut *writer, &record).and_then(|_| writer.flush()));
+            }
+        }
+    }
+}
+
+impl Sink {
+    fn new(writer: Box<dyn Write + Send>) -> Self {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        return Self::Inline(Arc::new(Mutex::new(writer)));
+
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            let (tx, rx) = mpsc::channel();
+            let (writer_tx, writer_rx) = mpsc::sync_channel(1);
+            let thread = std::thread::Builder::new()
+                .name("rspc-recorder".into())
+                .spawn(move || {
+                    // The writer is only moved into the thread once it's known to be running so it can be used inline otherwise.
+                    let Ok(mut writer) = writer_rx.recv() else {
+                        return;
+                    };
+                    write_batches(&mut writer, rx);
+                });
+
+            match thread {
+                Ok(_) => {
+                    writer_tx.send(writer).ok();
+                    Self::Thread(tx)
+                }
+                Err(_err) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::error!(
+                        "Error spawning rspc recording thread, writing inline: {}",
+                        _err
+                    );
+
+                    Self::Inline(Arc::new(Mutex::new(writer)))
+                }
+            }
+        }
+    }
+}
+
+/// Write every queued record before flushing the writer. Runs until every [`Recorder`] using the queue is dropped.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn write_batches(writer: &mut dyn Write, rx: mpsc::Receiver<Message>) {
+    while let Ok(message) = rx.recv() {
+        let mut waiting = Vec::new();
+        for message in std::iter::once(message).chain(rx.try_iter().take(MAX_BATCH - 1)) {
+            match message {
+                Message::Record(record) => log_err(write_record(writer, &record)),
+                Message::Flush(done) => waiting.push(done),
+            }
+        }
+
+        log_err(writer.flush());
+        for done in waiting {
+            done.send(()).ok();
+        }
+    }
+}
+
+fn write_record(writer: &mut dyn Write, record: &Record) -> io::Result<()> {
+    serde_json::to_writer(&mut *writer, record)?;
+    writer.write_all(b"\n")
+}
+
+fn log_err(result: io::Result<()>) {
+    if let Err(_err) = result {
+        #[cfg(feature = "tracing")]
+        tracing::error!("Error writing to rspc recording: {}", _err);
+    }
+}
+
+/// Milliseconds since the Unix epoch. [`SystemTime`](std::time::SystemTime) panics on `wasm32-unknown-unknown` so the JavaScript clock is used there.
+fn now_millis() -> u64 {
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    return js_sys::Date::now() as u64;
+
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+impl fmt::Debug for Recorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Recorder")
+            .field("redact", &self.redact.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Record {
+    /// The request if this is a record of a request.
+    pub fn request_mut(&mut self) -> Option<&mut Value> {
+        match &mut self.message {
+            RecordedMessage::Request(req) => Some(req),
+            RecordedMessage::Response(_) => None,
+        }
+    }
+
+    /// The response if this is a record of a response.
+    pub fn response_mut(&mut self) -> Option<&mut Value> {
+        match &mut self.message {
+            RecordedMessage::Request(_) => None,
+            RecordedMessage::Response(resp) => Some(resp),
+        }
+    }
+}
+
+/// Read a recording written by a [`Recorder`].
+pub fn read_recording(path: impl AsRef<Path>) -> io::Result<Vec<Record>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| Ok(serde_json::from_str(&line?)?))
+        .collect()
+}
+
+#[cfg(feature = "testing")]
+mod replay {
+    use std::{collections::BTreeMap, fmt, sync::Arc, time::Duration};
+
+    use futures::future::{select, Either};
+    use serde_json::Value;
+
+    use crate::{
+        internal::exec::{AsyncRuntime, Executor, IncomingMessage},
+        testing::MockConnection,
+        BuiltRouter,
+    };
+
+    use super::{Record, RecordedMessage};
+
+    /// The responses to a request which differ between a recording and its replay.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ReplayDiff {
+        pub connection: u64,
+        pub id: u32,
+        pub expected: Vec<Value>,
+        pub actual: Vec<Value>,
+    }
+
+    impl fmt::Display for ReplayDiff {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            writeln!(f, "connection {} request {}:", self.connection, self.id)?;
+            for (label, values) in [("expected", &self.expected), ("actual", &self.actual)] {
+                write!(f, "  {label}:")?;
+                for value in values {
+                    write!(f, " {value}")?;
+                }
+                writeln!(f)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Replay a recording against `router` and return the responses which differ from the recording.
+    ///
+    /// Each connection is replayed in-memory with the context returned by `ctx_fn` for its id.
+    /// Requests are sent in the recorded order, waiting up to `timeout` (measured using [`AsyncRuntime::sleep`] of the runtime `R`) for the responses that were recorded before each one.
+    /// Responses are compared per request so the order of responses to different requests doesn't matter.
+    pub async fn replay<R, TCtx>(
+        router: Arc<BuiltRouter<TCtx>>,
+        ctx_fn: impl Fn(u64) -> TCtx,
+        records: impl IntoIterator<Item = Record>,
+        timeout: Duration,
+    ) -> Vec<ReplayDiff>
+    where
+        R: AsyncRuntime,
+        TCtx: Clone + Send + 'static,
+    {
+        let mut connections = BTreeMap::<_, Vec<_>>::new();
+        for record in records {
+            connections
+                .entry(record.connection)
+                .or_default()
+                .push(record.message);
+        }
+
+        let mut diffs = Vec::new();
+        for (connection, messages) in connections {
+            let mut conn = MockConnection::new(Executor::new(router.clone()), ctx_fn(connection));
+            let mut expected = Vec::new();
+            let mut actual = Vec::new();
+            let mut timed_out = false;
+
+            for message in messages {
+                match message {
+                    RecordedMessage::Request(req) => conn.send_raw(IncomingMessage::Msg(Ok(req))),
+                    RecordedMessage::Response(resp) => {
+                        expected.push(resp);
+
+                        // So the following requests are sent in the same state as they were recorded.
+                        // The same timer is polled until it fires as `Instant` isn't available on every runtime.
+                        let mut sleep = Box::pin(R::sleep(timeout));
+                        while !timed_out && actual.len() < expected.len() {
+                            match select(Box::pin(conn.recv()), sleep.as_mut()).await {
+                                Either::Left((Some(msg), _)) => actual.extend(msg),
+                                _ => timed_out = true,
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Collect anything which was sent in response to the last requests.
+            conn.close();
+            if !timed_out {
+                while let Some(msg) = conn.recv().await {
+                    actual.extend(msg);
+                }
+            }
+
+            let actual = actual
+                .into_iter()
+                .filter_map(|resp| serde_json::to_value(resp).ok())
+                .collect();
+            diffs.extend(diff(connection, expected, actual));
+        }
+
+        diffs
+    }
+
+    fn diff(connection: u64, expected: Vec<Value>, actual: Vec<Value>) -> Vec<ReplayDiff> {
+        let mut by_id = BTreeMap::<u32, (Vec<_>, Vec<_>)>::new();
+        for (value, is_expected) in expected
+            .into_iter()
+            .map(|v| (v, true))
+            .chain(actual.into_iter().map(|v| (v, false)))
+        {
+            let id = value.get("id").and_then(Value::as_u64).unwrap_or_default() as u32;
+            let entry = by_id.entry(id).or_default();
+            match is_expected {
+                true => entry.0.push(value),
+                false => entry.1.push(value),
+            }
+        }
+
+        by_id
+            .into_iter()
+            .filter(|(_, (expected, actual))| expected != actual)
+            .map(|(id, (expected, actual))| ReplayDiff {
+                connection,
+                id,
+                expected,
+                actual,
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub use replay::*;
@@ -1,31 +1,47 @@
-use std::{borrow::Cow, panic::Location};
+use std::{borrow::Cow, error, fmt, ops::Deref, panic::Location};
 
 use thiserror::Error;
 
-use crate::BuiltRouter;
+use crate::{internal::middleware::ProcedureKind, BuiltRouter};
 
-/// TODO
-#[derive(Debug, PartialEq, Eq)]
+/// An error with a procedure or router which was found while building the router.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BuildError {
     pub(crate) cause: BuildErrorCause,
-    #[cfg(debug_assertions)]
     pub(crate) name: Cow<'static, str>,
-    #[cfg(debug_assertions)]
     pub(crate) loc: &'static Location<'static>,
 }
 
 impl BuildError {
-    /// DO NOT USE IT, it's for unit testing only and may change without a major version bump.
-    #[doc(hidden)]
-    #[cfg(debug_assertions)]
-    pub fn expose(&self) -> (String, String) {
-        (self.name.to_string(), self.cause.to_string())
+    /// What is wrong.
+    pub fn cause(&self) -> &BuildErrorCause {
+        &self.cause
+    }
+
+    /// The full key of the procedure or the prefix of the router the error is for.
+    pub fn key(&self) -> &str {
+        &self.name
+    }
+
+    /// Where the procedure or router was defined.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.loc
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' at '{}': {}", self.name, self.loc, self.cause)
     }
 }
 
+impl error::Error for BuildError {}
+
+/// Why a procedure or router is invalid.
 #[allow(clippy::enum_variant_names)]
-#[derive(Debug, Error, PartialEq, Eq)]
-pub(crate) enum BuildErrorCause {
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildErrorCause {
     #[error(
         "a procedure or router name must be more than 1 character and less than 255 characters"
     )]
@@ -36,46 +52,69 @@ pub(crate) enum BuildErrorCause {
         "a procedure or router name is using the name '{0}' which is reserved for internal use."
     )]
     ReservedName(String),
+    #[error("a {} with the same key is already defined at '{1}'", .0.to_str())]
+    DuplicateKey(ProcedureKind, &'static Location<'static>),
+    #[error("the type '{0}' has the same name as a different type which was first used by the procedure defined at '{1}'. Types must have unique names so they can be exported")]
+    DuplicateTypeName(&'static str, &'static Location<'static>),
+    #[error("the router merged under this prefix has no procedures. It was created at '{0}'")]
+    EmptyRouter(&'static Location<'static>),
 }
 
-/// TODO
-pub enum BuildResult<TCtx: 'static> {
-    Ok(BuiltRouter<TCtx>),
-    Err(Vec<BuildError>),
+/// Every error found while building a router.
+///
+/// The `Debug` implementation is the same as `Display` so `.unwrap()` on a [`BuildResult`] prints a readable message.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BuildErrors(pub(crate) Vec<BuildError>);
+
+impl BuildErrors {
+    pub fn into_vec(self) -> Vec<BuildError> {
+        self.0
+    }
 }
 
-impl<TCtx: 'static> PartialEq for BuildResult<TCtx> {
-    fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (Self::Ok(_), Self::Ok(_)) => true,
-            (Self::Err(e), Self::Err(e2)) => e == e2,
-            _ => false,
-        }
+impl Deref for BuildErrors {
+    type Target = [BuildError];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-impl<TCtx: 'static> BuildResult<TCtx> {
-    pub fn unwrap(self) -> BuiltRouter<TCtx> {
-        match self {
-            Self::Ok(router) => router,
-            Self::Err(errors) => {
-                #[cfg(debug_assertions)]
-                {
-                    eprintln!("Error building rspc router\n");
-
-                    for error in &errors {
-                        eprintln!("Error at '{}' with procedure '{}':", error.loc, error.name);
-                        eprintln!("\t error: {}\n", error.cause);
-                    }
-                }
-
-                #[cfg(not(debug_assertions))]
-                {
-                    eprintln!("Error building rspc router. Please use a debug build for proper error reporting.\n");
-                }
-
-                std::process::exit(1);
-            }
+impl IntoIterator for BuildErrors {
+    type Item = BuildError;
+    type IntoIter = std::vec::IntoIter<BuildError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a BuildErrors {
+    type Item = &'a BuildError;
+    type IntoIter = std::slice::Iter<'a, BuildError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for BuildErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error building rspc router")?;
+        for error in &self.0 {
+            write!(f, "\n  - {error}")?;
         }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BuildErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
     }
 }
+
+impl error::Error for BuildErrors {}
+
+/// The result of [`Router::build`](crate::Router::build).
+pub type BuildResult<TCtx> = Result<BuiltRouter<TCtx>, BuildErrors>;
@@ -1,16 +1,26 @@
-use std::{borrow::Cow, panic::Location};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData,
+    panic::Location,
+    sync::Arc,
+};
 
 use serde::de::DeserializeOwned;
-use specta::{Type, TypeDefs};
+use specta::{DataType, Type, TypeDefs, TypeSid};
 
 use crate::{
+    export::{walk_item_references, walk_references},
     internal::{
-        is_valid_name,
-        middleware::MiddlewareBuilder,
+        is_valid_name, is_valid_procedure_key,
+        middleware::{
+            ConstrainedMiddleware, MapCtxLayer, MiddlewareBuilder, MiddlewareLayer,
+            SharedMiddleware,
+        },
         procedure::{BuildProceduresCtx, Procedure},
-        HasResolver, ProcedureStore, RequestLayer,
+        DynLayer, HasResolver, ProcedureStore, ProcedureTodo, RequestLayer, SealedLayer,
     },
-    BuildError, BuildResult, BuiltRouter,
+    BuildError, BuildErrorCause, BuildErrors, BuildResult, BuiltRouter, Error,
 };
 
 pub type ProcedureBuildFn<TCtx> =
@@ -18,22 +28,30 @@ pub type ProcedureBuildFn<TCtx> =
 
 pub struct Router<TCtx>
 where
-    TCtx: Send + Sync + 'static,
+    TCtx: Send + 'static,
 {
-    procedures: Vec<(Cow<'static, str>, ProcedureBuildFn<TCtx>)>,
+    procedures: Vec<(
+        Cow<'static, str>,
+        &'static Location<'static>,
+        ProcedureBuildFn<TCtx>,
+    )>,
     errors: Vec<BuildError>,
+    // Where the router was created. Used to report build errors.
+    loc: &'static Location<'static>,
 }
 
 impl<TCtx> Router<TCtx>
 where
-    TCtx: Send + Sync + 'static,
+    TCtx: Send + 'static,
 {
     /// Constructs a new `Router`.
     /// Avoid using this directly, use `Rspc::router` instead so the types can be inferred.
+    #[track_caller]
     pub(crate) fn _internal_new() -> Self {
         Self {
             procedures: Vec::new(),
             errors: Vec::new(),
+            loc: Location::caller(),
         }
     }
 
@@ -53,18 +71,17 @@ where
         TResultMarker: 'static,
         TMiddleware: MiddlewareBuilder<Ctx = TCtx>,
     {
-        if let Some(cause) = is_valid_name(key) {
+        if let Some(cause) = is_valid_procedure_key(key) {
             self.errors.push(BuildError {
                 cause,
-                #[cfg(debug_assertions)]
                 name: Cow::Borrowed(key),
-                #[cfg(debug_assertions)]
                 loc: Location::caller(),
             });
         }
 
         self.procedures.push((
             Cow::Borrowed(key),
+            Location::caller(),
             Box::new(|full_key, ctx| procedure.build(full_key, ctx)),
         ));
 
@@ -72,43 +89,163 @@ where
     }
 
     #[track_caller]
-    #[allow(unused_mut)]
-    pub fn merge(mut self, prefix: &'static str, mut r: Router<TCtx>) -> Self {
+    pub fn merge(mut self, prefix: &'static str, r: Router<TCtx>) -> Self {
         if let Some(cause) = is_valid_name(prefix) {
             self.errors.push(BuildError {
                 cause,
-                #[cfg(debug_assertions)]
                 name: Cow::Borrowed(prefix),
-                #[cfg(debug_assertions)]
                 loc: Location::caller(),
             });
         }
 
-        #[cfg(not(debug_assertions))]
-        {
-            self.errors.append(&mut r.errors);
+        if r.procedures.is_empty() && r.errors.is_empty() {
+            self.errors.push(BuildError {
+                cause: BuildErrorCause::EmptyRouter(r.loc),
+                name: Cow::Borrowed(prefix),
+                loc: Location::caller(),
+            });
         }
 
-        #[cfg(debug_assertions)]
-        {
-            self.errors.extend(&mut r.errors.into_iter().map(|mut err| {
-                err.name = Cow::Owned(format!("{}.{}", prefix, err.name));
-                err
-            }));
-        }
+        self.errors.extend(r.errors.into_iter().map(|mut err| {
+            err.name = Cow::Owned(format!("{}.{}", prefix, err.name));
+            err
+        }));
 
         self.procedures.extend(
             r.procedures
                 .into_iter()
-                .map(|(name, p)| (Cow::Owned(format!("{}.{}", prefix, name)), p)),
+                .map(|(name, loc, p)| (Cow::Owned(format!("{}.{}", prefix, name)), loc, p)),
         );
 
         self
     }
 
+    /// Apply a middleware to every procedure in this router.
+    ///
+    /// The middleware runs before any middleware defined on the procedures themselves. It can also switch the context,
+    /// so a router written against a narrower context (eg. one with an authenticated user) can be merged into a router with the parent context.
+    ///
+    /// ```rust
+    /// use rspc::{Error, ErrorCode, Rspc, Router};
+    ///
+    /// #[derive(Clone)]
+    /// struct Ctx {
+    ///     user_id: Option<u32>,
+    /// }
+    ///
+    /// struct AuthedCtx {
+    ///     user_id: u32,
+    /// }
+    ///
+    /// const R: Rspc<Ctx> = Rspc::new();
+    /// const AR: Rspc<AuthedCtx> = Rspc::new();
+    ///
+    /// let users: Router<Ctx> = AR
+    ///     .router()
+    ///     .procedure("me", AR.query(|ctx, _: ()| ctx.user_id))
+    ///     .with(|mw, ctx: Ctx| async move {
+    ///         match ctx.user_id {
+    ///             Some(user_id) => Ok(mw.next(AuthedCtx { user_id })),
+    ///             None => Err(Error::new(ErrorCode::Unauthorized, "not logged in".into())),
+    ///         }
+    ///     });
+    ///
+    /// let router = R.router().merge("users", users).build().unwrap();
+    /// ```
+    pub fn with<TParentCtx, Mw>(self, mw: Mw) -> Router<TParentCtx>
+    where
+        TParentCtx: Send + 'static,
+        Mw: ConstrainedMiddleware<TParentCtx, NewCtx = TCtx>,
+    {
+        let mw = SharedMiddleware(Arc::new(mw));
+        self.map_layers(move |next| {
+            MiddlewareLayer {
+                next,
+                mw: mw.clone(),
+                phantom: PhantomData,
+            }
+            .erase()
+        })
+    }
+
+    /// Merge a router which was defined against a different context into this router.
+    ///
+    /// `map` is called with the context of every request to one of the merged procedures to create the context they expect.
+    /// If it returns an error the procedure is not called and the error is returned to the client.
+    ///
+    /// ```rust
+    /// use rspc::{Error, ErrorCode, Rspc};
+    ///
+    /// struct Ctx {
+    ///     db: Option<String>,
+    /// }
+    ///
+    /// const R: Rspc<Ctx> = Rspc::new();
+    /// const DR: Rspc<String> = Rspc::new();
+    ///
+    /// let db = DR.router().procedure("name", DR.query(|db, _: ()| db));
+    ///
+    /// let router = R
+    ///     .router()
+    ///     .merge_with("db", db, |ctx: Ctx| {
+    ///         ctx.db
+    ///             .ok_or_else(|| Error::new(ErrorCode::InternalServerError, "no database".into()))
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    #[track_caller]
+    pub fn merge_with<TChildCtx, F>(
+        self,
+        prefix: &'static str,
+        r: Router<TChildCtx>,
+        map: F,
+    ) -> Self
+    where
+        TChildCtx: Send + 'static,
+        F: Fn(TCtx) -> Result<TChildCtx, Error> + Send + Sync + 'static,
+    {
+        let map = Arc::new(map);
+        self.merge(
+            prefix,
+            r.map_layers(move |next| {
+                MapCtxLayer {
+                    next,
+                    map: map.clone(),
+                }
+                .erase()
+            }),
+        )
+    }
+
+    /// Wrap the layer of every procedure when it's built so the router can be used with a different context.
+    fn map_layers<TParentCtx>(
+        self,
+        map: impl Fn(Arc<dyn DynLayer<TCtx>>) -> Arc<dyn DynLayer<TParentCtx>> + Clone + 'static,
+    ) -> Router<TParentCtx>
+    where
+        TParentCtx: Send + 'static,
+    {
+        Router {
+            procedures: self
+                .procedures
+                .into_iter()
+                .map(|(key, loc, build_fn)| {
+                    let map = map.clone();
+                    let build_fn: ProcedureBuildFn<TParentCtx> =
+                        Box::new(move |key, ctx| ctx.build_mapped(|ctx| build_fn(key, ctx), map));
+
+                    (key, loc, build_fn)
+                })
+                .collect(),
+            errors: self.errors,
+            loc: self.loc,
+        }
+    }
+
     pub fn build(self) -> BuildResult<TCtx> {
         if !self.errors.is_empty() {
-            return BuildResult::Err(self.errors);
+            return Err(BuildErrors(self.errors));
         }
 
         // TODO: Eventually take these as an argument so we can access the plugin store from the parent router -> For this we do this for compat
@@ -117,18 +254,44 @@ where
         let mut subscriptions = ProcedureStore::new("subscriptions"); // TODO: Take in as arg
         let mut typ_store = TypeDefs::new(); // TODO: Take in as arg
 
+        let mut errors = Vec::new();
         let mut ctx = BuildProceduresCtx {
             ty_store: &mut typ_store,
             queries: &mut queries,
             mutations: &mut mutations,
             subscriptions: &mut subscriptions,
+            errors: &mut errors,
+            loc: self.loc,
+            index: 0,
         };
 
-        for (key, build_fn) in self.procedures.into_iter() {
+        for (index, (key, loc, build_fn)) in self.procedures.into_iter().enumerate() {
+            ctx.loc = loc;
+            ctx.index = index;
             // TODO: Pass in the `key` here with the router merging prefixes already applied so it's the final runtime key
             (build_fn)(key, &mut ctx);
         }
 
+        // Types with the same name would collide when exported. This is checked once every procedure is built so the types are only scanned once.
+        let mut types_by_name = BTreeMap::<_, Vec<_>>::new();
+        for (sid, ty) in typ_store.iter() {
+            if let Some(ty) = ty {
+                types_by_name.entry(ty.name).or_default().push(*sid);
+            }
+        }
+        types_by_name.retain(|_, sids| sids.len() > 1);
+        if !types_by_name.is_empty() {
+            errors.extend(duplicate_type_errors(
+                &types_by_name,
+                &typ_store,
+                [&queries, &mutations, &subscriptions],
+            ));
+        }
+
+        if !errors.is_empty() {
+            return Err(BuildErrors(errors));
+        }
+
         let router = BuiltRouter {
             queries,
             mutations,
@@ -136,6 +299,74 @@ where
             typ_store,
         };
 
-        BuildResult::Ok(router)
+        Ok(router)
+    }
+}
+
+/// Report every procedure which uses a different type with the same name as a type used by a procedure registered before it.
+///
+/// This walks the types of every procedure so it's only done once duplicates are known to exist.
+fn duplicate_type_errors<TCtx: 'static>(
+    types_by_name: &BTreeMap<&'static str, Vec<TypeSid>>,
+    typ_store: &TypeDefs,
+    stores: [&ProcedureStore<TCtx>; 3],
+) -> Vec<BuildError> {
+    // The first procedure, by the order they were registered in, which uses each type
+    let mut used_by = BTreeMap::<TypeSid, (&str, &ProcedureTodo<TCtx>)>::new();
+    for (key, procedure) in stores.iter().flat_map(|store| store.store.iter()) {
+        for sid in referenced_types([&procedure.ty.input, &procedure.ty.result], typ_store) {
+            let first = used_by.entry(sid).or_insert((key, procedure));
+            if procedure.index < first.1.index {
+                *first = (key, procedure);
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    for (&name, sids) in types_by_name {
+        let mut users = sids
+            .iter()
+            .filter_map(|sid| used_by.get(sid).copied())
+            .collect::<Vec<_>>();
+        users.sort_by_key(|(_, procedure)| procedure.index);
+
+        if let Some(((_, first), rest)) = users.split_first() {
+            errors.extend(rest.iter().map(|(key, procedure)| BuildError {
+                cause: BuildErrorCause::DuplicateTypeName(name, first.loc),
+                name: Cow::Owned(key.to_string()),
+                loc: procedure.loc,
+            }));
+        }
+    }
+
+    errors
+}
+
+/// Collect the named types `tys` reference, including the ones referenced through their definitions.
+fn referenced_types<'a>(
+    tys: impl IntoIterator<Item = &'a DataType>,
+    typ_store: &TypeDefs,
+) -> BTreeSet<TypeSid> {
+    let mut sids = BTreeSet::new();
+    let mut pending = Vec::new();
+    for ty in tys {
+        walk_references(ty, &mut |r| {
+            if sids.insert(r.sid) {
+                pending.push(r.sid);
+            }
+        });
+    }
+
+    // Recursive types reference themselves so each definition is only walked once
+    while let Some(sid) = pending.pop() {
+        if let Some(Some(def)) = typ_store.get(&sid) {
+            walk_item_references(&def.item, &mut |r| {
+                if sids.insert(r.sid) {
+                    pending.push(r.sid);
+                }
+            });
+        }
     }
+
+    sids
 }
@@ -23,15 +23,15 @@ use crate::{
 pub struct Rspc<
     TCtx = (), // The is the context the current router was initialised with
 > where
-    TCtx: Send + Sync + 'static,
+    TCtx: Send + 'static,
 {
-    phantom: PhantomData<TCtx>,
+    phantom: PhantomData<fn() -> TCtx>,
 }
 
 #[allow(clippy::new_without_default)]
 impl<TCtx> Rspc<TCtx>
 where
-    TCtx: Send + Sync + 'static,
+    TCtx: Send + 'static,
 {
     pub const fn new() -> Self {
         Self {
@@ -57,8 +57,9 @@ macro_rules! resolver {
 
 impl<TCtx> Rspc<TCtx>
 where
-    TCtx: Send + Sync + 'static,
+    TCtx: Send + 'static,
 {
+    #[track_caller]
     pub fn router(&self) -> Router<TCtx> {
         Router::_internal_new()
     }
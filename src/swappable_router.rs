@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use arc_swap::{ArcSwap, Guard};
+
+use crate::BuiltRouter;
+
+/// A handle to a [`BuiltRouter`] which can be replaced while it's being served.
+///
+/// Every clone of the handle shares the same router so the integrations and any executors created from it pick up the new router on their next request.
+/// Requests and subscriptions which are already running continue to use the router they were started with until they finish, so no connections are dropped.
+///
+/// Loading the router is lock-free so it's cheap to do on every request.
+///
+/// ```rust
+/// use rspc::{Rspc, SwappableRouter};
+///
+/// const R: Rspc<()> = Rspc::new();
+///
+/// let router = SwappableRouter::new(
+///     R.router()
+///         .procedure("version", R.query(|_, _: ()| "1.0.0"))
+///         .build()
+///         .unwrap()
+///         .arced(),
+/// );
+///
+/// // Pass `router.clone()` to an integration and then at any point later:
+/// router.swap(
+///     R.router()
+///         .procedure("version", R.query(|_, _: ()| "2.0.0"))
+///         .build()
+///         .unwrap()
+///         .arced(),
+/// );
+/// ```
+pub struct SwappableRouter<TCtx = ()> {
+    current: Arc<ArcSwap<BuiltRouter<TCtx>>>,
+}
+
+impl<TCtx> Clone for SwappableRouter<TCtx> {
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current.clone(),
+        }
+    }
+}
+
+impl<TCtx> SwappableRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    pub fn new(router: Arc<BuiltRouter<TCtx>>) -> Self {
+        Self {
+            current: Arc::new(ArcSwap::new(router)),
+        }
+    }
+
+    /// Get the router which new requests are currently executed against.
+    pub fn load(&self) -> Arc<BuiltRouter<TCtx>> {
+        self.current.load_full()
+    }
+
+    /// Borrow the current router without touching its reference count. Used on the hot path of the executor.
+    pub(crate) fn current(&self) -> Guard<Arc<BuiltRouter<TCtx>>> {
+        self.current.load()
+    }
+
+    /// Atomically replace the router and return the previous one.
+    pub fn swap(&self, router: Arc<BuiltRouter<TCtx>>) -> Arc<BuiltRouter<TCtx>> {
+        self.current.swap(router)
+    }
+}
+
+impl<TCtx> From<Arc<BuiltRouter<TCtx>>> for SwappableRouter<TCtx>
+where
+    TCtx: Send + 'static,
+{
+    fn from(router: Arc<BuiltRouter<TCtx>>) -> Self {
+        Self::new(router)
+    }
+}
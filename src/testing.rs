@@ -0,0 +1,525 @@
+//! Utilities for testing routers and middleware.
+//!
+//! [`exec`] executes a single request and [`MockConnection`] drives the same connection which powers websockets and Tauri in-memory.
+//! The connection runs on a virtual clock so batching is deterministic and the responses can be checked with [`assert_responses`].
+//! The exported bindings can be compared against a file using [`assert_snapshot`].
+//!
+//! ```rust
+//! use rspc::{
+//!     internal::exec::Executor,
+//!     testing::{self, MockConnection},
+//!     Rspc,
+//! };
+//!
+//! const R: Rspc<()> = Rspc::new();
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let router = R
+//!     .router()
+//!     .procedure("version", R.query(|_, _: ()| "1.0.0"))
+//!     .procedure(
+//!         "count",
+//!         R.subscription(|_, _: ()| futures::stream::iter(0..2)),
+//!     )
+//!     .build()
+//!     .unwrap()
+//!     .arced();
+//!
+//! let mut conn = MockConnection::new(Executor::new(router), ());
+//! conn.send(testing::query(0, "version", ()));
+//! conn.send(testing::subscription(1, "count", ()));
+//!
+//! testing::assert_responses(
+//!     &conn.recv_responses(4).await,
+//!     &[
+//!         testing::value(0, "1.0.0"),
+//!         testing::value(1, 0),
+//!         testing::value(1, 1),
+//!         testing::complete(1),
+//!     ],
+//! );
+//! # }
+//! ```
+#![allow(clippy::panic)] // Panicking is how a test fails
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    convert::Infallible,
+    env, fs,
+    future::Future,
+    ops::Bound,
+    path::Path,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::{future::poll_fn, task::noop_waker_ref, Sink, Stream};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    export::Exporter,
+    internal::exec::{
+        self, AsyncRuntime, ConnectionTask, Executor, ExecutorResult, IncomingMessage,
+        NoOpSubscriptionManager, Request, Response, ResponseError, ResponseInner,
+    },
+    BuiltRouter, ErrorCode,
+};
+
+/// How long a connection waits for more responses before sending a batch.
+pub const BATCH_TIMEOUT: Duration = exec::BATCH_TIMEOUT;
+
+/// Construct a query request.
+pub fn query(id: u32, path: &str, input: impl Serialize) -> Request {
+    Request::Query {
+        id,
+        path: Cow::Owned(path.to_string()),
+        input: Some(to_value(input)),
+    }
+}
+
+/// Construct a mutation request.
+pub fn mutation(id: u32, path: &str, input: impl Serialize) -> Request {
+    Request::Mutation {
+        id,
+        path: Cow::Owned(path.to_string()),
+        input: Some(to_value(input)),
+    }
+}
+
+/// Construct a request to start a subscription.
+pub fn subscription(id: u32, path: &str, input: impl Serialize) -> Request {
+    Request::Subscription {
+        id,
+        path: Cow::Owned(path.to_string()),
+        input: Some(to_value(input)),
+    }
+}
+
+/// Construct a request to stop a subscription.
+pub fn subscription_stop(id: u32) -> Request {
+    Request::SubscriptionStop { id }
+}
+
+/// Construct a successful response.
+pub fn value(id: u32, value: impl Serialize) -> Response {
+    Response {
+        id,
+        inner: ResponseInner::Value(to_value(value)),
+    }
+}
+
+/// Construct an error response without any data.
+pub fn error(id: u32, code: ErrorCode, message: impl Into<String>) -> Response {
+    Response {
+        id,
+        inner: ResponseInner::Error(ResponseError {
+            code: code.to_status_code(),
+            message: message.into(),
+            data: None,
+        }),
+    }
+}
+
+/// Construct the response which ends a subscription.
+pub fn complete(id: u32) -> Response {
+    Response {
+        id,
+        inner: ResponseInner::Complete,
+    }
+}
+
+fn to_value(value: impl Serialize) -> Value {
+    serde_json::to_value(value).unwrap_or_else(|err| panic!("error serializing value: {err}"))
+}
+
+/// Execute a request which isn't a subscription. Returns `None` if the request doesn't produce a response, eg. [`subscription_stop`].
+pub async fn exec<TCtx: Send + 'static>(
+    executor: &Executor<TCtx>,
+    ctx: TCtx,
+    req: Request,
+) -> Option<Response> {
+    match executor.execute(ctx, req, &mut (None as Option<NoOpSubscriptionManager>)) {
+        ExecutorResult::FutureResponse(fut) => Some(fut.await),
+        ExecutorResult::Response(resp) => Some(resp),
+        ExecutorResult::None => None,
+    }
+}
+
+/// Assert `actual` contains exactly the `expected` responses.
+///
+/// The responses to different requests can be in any order, as they are when batched, but the responses with the same id must be in the same order.
+#[track_caller]
+pub fn assert_responses(actual: &[Response], expected: &[Response]) {
+    fn by_id(responses: &[Response]) -> BTreeMap<u32, Vec<&ResponseInner>> {
+        let mut map = BTreeMap::<_, Vec<_>>::new();
+        for resp in responses {
+            map.entry(resp.id).or_default().push(&resp.inner);
+        }
+        map
+    }
+
+    assert_eq!(by_id(actual), by_id(expected), "responses don't match");
+}
+
+/// Assert `actual` matches the snapshot file at `path`.
+///
+/// The snapshot is created if it doesn't exist and overwritten if the `RSPC_UPDATE_SNAPSHOTS` environment variable is set.
+#[track_caller]
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+    if env::var_os("RSPC_UPDATE_SNAPSHOTS").is_some() || !path.exists() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap_or_else(|err| {
+                panic!("error creating directory '{}': {err}", dir.display())
+            });
+        }
+        fs::write(path, actual)
+            .unwrap_or_else(|err| panic!("error writing snapshot '{}': {err}", path.display()));
+        return;
+    }
+
+    let expected = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("error reading snapshot '{}': {err}", path.display()))
+        // So the snapshots still match if git converted their line endings.
+        .replace("\r\n", "\n");
+    if expected == actual {
+        return;
+    }
+
+    let (mut expected_lines, mut actual_lines) = (expected.lines(), actual.lines());
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (e, a) if e == a && e.is_some() => line += 1,
+            (e, a) => panic!(
+                "snapshot '{}' doesn't match at line {line}:\n  expected: {:?}\n  actual:   {:?}\nSet `RSPC_UPDATE_SNAPSHOTS=1` to update it.",
+                path.display(),
+                e.unwrap_or_default(),
+                a.unwrap_or_default()
+            ),
+        }
+    }
+}
+
+/// Export the bindings of a router using `exporter` and assert they match the snapshot file at `path`. Refer to [`assert_snapshot`].
+#[track_caller]
+pub fn assert_export_snapshot<TCtx: Send + 'static>(
+    router: &BuiltRouter<TCtx>,
+    exporter: &impl Exporter,
+    path: impl AsRef<Path>,
+) {
+    match router.export_to_string(exporter) {
+        Ok(bindings) => assert_snapshot(path, &bindings),
+        Err(err) => panic!("error exporting bindings: {err}"),
+    }
+}
+
+/// Export the Typescript bindings of a router with the default configuration and assert they match the snapshot file at `path`. Refer to [`assert_snapshot`].
+#[cfg(feature = "typescript")]
+#[track_caller]
+pub fn assert_ts_snapshot<TCtx: Send + 'static>(
+    router: &BuiltRouter<TCtx>,
+    path: impl AsRef<Path>,
+) {
+    assert_export_snapshot(router, &crate::export::Typescript::new(), path)
+}
+
+/// A connection which is driven in-memory, as if a client was connected over a websocket.
+///
+/// Time only passes on the connection's virtual clock. It's advanced by [`MockConnection::advance`] or automatically by [`MockConnection::recv`] whenever the connection is waiting on a timer.
+pub struct MockConnection<TCtx> {
+    task: Pin<Box<ConnectionTask<MockRuntime, TCtx, MockSocket, Infallible>>>,
+    socket: MockSocket,
+    clock: Clock,
+    start: Instant,
+    done: bool,
+}
+
+impl<TCtx> MockConnection<TCtx>
+where
+    TCtx: Clone + Send + 'static,
+{
+    /// Create a connection which executes requests using `executor` with the context `ctx`.
+    pub fn new(executor: Executor<TCtx>, ctx: TCtx) -> Self {
+        let socket = MockSocket::default();
+        let clock = Clock::new();
+
+        Self {
+            task: Box::pin(ConnectionTask::new(ctx, executor, socket.clone(), None)),
+            socket,
+            start: clock.now(),
+            clock,
+            done: false,
+        }
+    }
+
+    /// Send a request to the connection. It's executed the next time the connection is polled.
+    pub fn send(&self, req: Request) {
+        self.send_raw(IncomingMessage::Msg(serde_json::to_value(req)));
+    }
+
+    /// Send multiple requests in a single message.
+    pub fn send_batch(&self, reqs: impl IntoIterator<Item = Request>) {
+        let reqs = reqs.into_iter().collect::<Vec<_>>();
+        self.send_raw(IncomingMessage::Msg(serde_json::to_value(reqs)));
+    }
+
+    /// Send a message as it would be received from the transport. Eg. invalid JSON.
+    pub fn send_raw(&self, msg: IncomingMessage) {
+        self.socket.lock().incoming.push_back(msg);
+    }
+
+    /// Close the connection. Every running subscription is stopped.
+    pub fn close(&self) {
+        self.socket.lock().closed = true;
+    }
+
+    /// Move the virtual clock forward. Poll the connection with [`MockConnection::try_recv`] to run the timers which are now due.
+    pub fn advance(&self, duration: Duration) {
+        self.clock.lock().now += duration;
+    }
+
+    /// How long the virtual clock has advanced since the connection was created.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now() - self.start
+    }
+
+    /// Poll the connection once without advancing the clock and return a message it sent, if any.
+    pub fn try_recv(&mut self) -> Option<Vec<Response>> {
+        self.poll_task(&mut Context::from_waker(noop_waker_ref()));
+        self.socket.take_sent()
+    }
+
+    /// Wait for the connection to send a message. If the connection is waiting on a timer the clock is advanced to it.
+    ///
+    /// Returns `None` once the connection has been closed and every message it sent has been received.
+    pub async fn recv(&mut self) -> Option<Vec<Response>> {
+        poll_fn(|cx| loop {
+            self.poll_task(cx);
+            if let Some(msg) = self.socket.take_sent() {
+                return Poll::Ready(Some(msg));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            if !self.clock.advance_to_next_timer() {
+                return Poll::Pending;
+            }
+        })
+        .await
+    }
+
+    /// Receive messages until at least `n` responses have been sent.
+    ///
+    /// Panics if the connection is closed before that.
+    pub async fn recv_responses(&mut self, n: usize) -> Vec<Response> {
+        let mut responses = Vec::with_capacity(n);
+        while responses.len() < n {
+            match self.recv().await {
+                Some(msg) => responses.extend(msg),
+                None => panic!(
+                    "connection closed after sending {} of {n} responses",
+                    responses.len()
+                ),
+            }
+        }
+        responses
+    }
+
+    fn poll_task(&mut self, cx: &mut Context<'_>) {
+        if self.done {
+            return;
+        }
+
+        let task = self.task.as_mut();
+        self.done = self.clock.enter(|| task.poll(cx)).is_ready();
+    }
+}
+
+#[derive(Default)]
+struct SocketState {
+    incoming: VecDeque<IncomingMessage>,
+    outgoing: VecDeque<String>,
+    closed: bool,
+}
+
+/// The transport of a [`MockConnection`]. It's only read when the connection is polled by its owner so it never needs to wake the task.
+#[derive(Clone, Default)]
+struct MockSocket(Arc<Mutex<SocketState>>);
+
+impl MockSocket {
+    fn lock(&self) -> MutexGuard<'_, SocketState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take_sent(&self) -> Option<Vec<Response>> {
+        let msg = self.lock().outgoing.pop_front()?;
+        Some(
+            serde_json::from_str(&msg)
+                .unwrap_or_else(|err| panic!("connection sent an invalid message '{msg}': {err}")),
+        )
+    }
+}
+
+impl Stream for MockSocket {
+    type Item = Result<IncomingMessage, Infallible>;
+
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut state = self.lock();
+        match state.incoming.pop_front() {
+            Some(msg) => Poll::Ready(Some(Ok(msg))),
+            None if state.closed => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Sink<String> for MockSocket {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: String) -> Result<(), Self::Error> {
+        self.lock().outgoing.push_back(item);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+struct ClockState {
+    now: Instant,
+    // The deadline of every pending sleep and how many sleeps share it.
+    timers: BTreeMap<Instant, usize>,
+}
+
+/// The virtual clock of a [`MockConnection`].
+#[derive(Clone)]
+struct Clock(Arc<Mutex<ClockState>>);
+
+thread_local! {
+    // The clock of the connection which is currently being polled. `AsyncRuntime` has no `self` so this is how `MockRuntime` finds it.
+    static CURRENT: RefCell<Option<Clock>> = const { RefCell::new(None) };
+}
+
+impl Clock {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(ClockState {
+            now: Instant::now(),
+            timers: BTreeMap::new(),
+        })))
+    }
+
+    fn current() -> Option<Self> {
+        CURRENT.with(|clock| clock.borrow().clone())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, ClockState> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn now(&self) -> Instant {
+        self.lock().now
+    }
+
+    /// Run `func` with this as the clock of the [`MockRuntime`].
+    fn enter<T>(&self, func: impl FnOnce() -> T) -> T {
+        struct Reset(Option<Clock>);
+
+        impl Drop for Reset {
+            fn drop(&mut self) {
+                CURRENT.with(|clock| *clock.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _reset = Reset(CURRENT.with(|clock| clock.replace(Some(self.clone()))));
+        func()
+    }
+
+    /// Advance the clock to the next pending timer. Returns `false` if there isn't one.
+    fn advance_to_next_timer(&self) -> bool {
+        let mut state = self.lock();
+        let next = state
+            .timers
+            .range((Bound::Excluded(state.now), Bound::Unbounded))
+            .next()
+            .map(|(deadline, _)| *deadline);
+
+        match next {
+            Some(deadline) => {
+                state.now = deadline;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// An [`AsyncRuntime`] for [`MockConnection`] whose time is the virtual clock of the connection being polled.
+pub(crate) struct MockRuntime;
+
+impl AsyncRuntime for MockRuntime {
+    type TaskHandle = ();
+    type SleepUtilFut = MockSleep;
+
+    fn spawn<F: Future<Output = ()> + Send + 'static>(_: F) -> Self::TaskHandle {
+        unreachable!("`MockConnection` polls its task itself so nothing is spawned");
+    }
+
+    fn cancel_task(_: Self::TaskHandle) {}
+
+    fn sleep_util(till: Instant) -> Self::SleepUtilFut {
+        let clock = Clock::current().unwrap_or_else(Clock::new);
+        *clock.lock().timers.entry(till).or_default() += 1;
+        MockSleep { clock, till }
+    }
+
+    fn now() -> Instant {
+        Clock::current()
+            .map(|clock| clock.now())
+            .unwrap_or_else(Instant::now)
+    }
+}
+
+/// A sleep on the virtual clock. It doesn't wake the task as the [`MockConnection`] polls again after advancing the clock.
+pub(crate) struct MockSleep {
+    clock: Clock,
+    till: Instant,
+}
+
+impl Future for MockSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.clock.now() >= self.till {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for MockSleep {
+    fn drop(&mut self) {
+        let mut state = self.clock.lock();
+        if let Some(count) = state.timers.get_mut(&self.till) {
+            *count -= 1;
+            if *count == 0 {
+                state.timers.remove(&self.till);
+            }
+        }
+    }
+}
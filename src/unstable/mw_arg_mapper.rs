@@ -48,8 +48,8 @@ impl<M: MwArgMapper + 'static> MwArgMapperMiddleware<M> {
         handler: impl Fn(MiddlewareContext, TLCtx, M::State) -> Fu + Send + Sync + 'static,
     ) -> impl Middleware<TLCtx, NewCtx = TNCtx>
     where
-        TLCtx: Send + Sync + 'static,
-        TNCtx: Send + Sync + 'static,
+        TLCtx: Send + 'static,
+        TNCtx: Send + 'static,
         Fu: Future<Output = R> + Send + Sync + 'static,
         R: MwV2Result<Ctx = TNCtx> + Send + 'static,
     {
@@ -82,7 +82,7 @@ mod private {
 
     impl<M, TLCtx, F, Fu, R> SealedMiddleware<TLCtx> for MiddlewareFnWithTypeMapper<M, F>
     where
-        TLCtx: Send + Sync + 'static,
+        TLCtx: Send + 'static,
         F: Fn(MiddlewareContext, TLCtx) -> Fu + Send + Sync + 'static,
         Fu: Future<Output = R> + Send + 'static,
         R: MwV2Result + Send + 'static,
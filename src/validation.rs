@@ -0,0 +1,109 @@
+use std::{borrow::Cow, collections::BTreeMap, error, fmt};
+
+use serde::Serialize;
+
+/// Validate the input of a procedure prior to the resolver being called.
+///
+/// Enable it for a procedure by calling `.validate()` after setting the resolver.
+///
+/// ```rust
+/// use rspc::{Rspc, Validate, ValidationError};
+///
+/// #[derive(serde::Deserialize, specta::Type)]
+/// struct CreateUser {
+///     name: String,
+/// }
+///
+/// impl Validate for CreateUser {
+///     fn validate(&self) -> Result<(), ValidationError> {
+///         let mut err = ValidationError::new();
+///         if self.name.is_empty() {
+///             err.add("name", "must not be empty");
+///         }
+///         err.into_result()
+///     }
+/// }
+///
+/// const R: Rspc<()> = Rspc::new();
+///
+/// let procedure = R.mutation(|_, user: CreateUser| user.name).validate();
+/// ```
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationError>;
+}
+
+/// The fields of a procedure's input which failed validation.
+///
+/// This is returned to the client as the `data` of a `BadRequest` error in the form `{ "fields": { "path.to[0].field": ["message"] } }`.
+/// Deserialization errors are returned in the same form so the frontend only has to handle one shape.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ValidationError {
+    fields: BTreeMap<String, Vec<Cow<'static, str>>>,
+}
+
+impl ValidationError {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an error message for the field at `path`. An empty path refers to the whole input.
+    pub fn add(&mut self, path: impl Into<String>, message: impl Into<Cow<'static, str>>) {
+        self.fields
+            .entry(path.into())
+            .or_default()
+            .push(message.into());
+    }
+
+    /// Same as [`ValidationError::add`] but can be chained.
+    pub fn with(mut self, path: impl Into<String>, message: impl Into<Cow<'static, str>>) -> Self {
+        self.add(path, message);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    pub fn fields(&self) -> &BTreeMap<String, Vec<Cow<'static, str>>> {
+        &self.fields
+    }
+
+    /// Returns `Ok(())` if no errors were added.
+    pub fn into_result(self) -> Result<(), Self> {
+        match self.is_empty() {
+            true => Ok(()),
+            false => Err(self),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "error validating procedure arguments")?;
+
+        for (path, messages) in &self.fields {
+            for message in messages {
+                match path.is_empty() {
+                    true => write!(f, "; {message}")?,
+                    false => write!(f, "; {path}: {message}")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for ValidationError {}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for ValidationError {
+    fn from(err: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        // `serde_path_to_error` uses `.` for the root of the input.
+        let path = match err.path().to_string() {
+            path if path == "." => String::new(),
+            path => path,
+        };
+
+        Self::new().with(path, err.into_inner().to_string())
+    }
+}
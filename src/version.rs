@@ -0,0 +1,100 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Which version of a procedure a request wants to call.
+///
+/// Procedures are versioned by suffixing their key with `@v<number>` when they are defined, eg. `get@v2`. Multiple versions of the same procedure can be registered side-by-side.
+/// The client requests a version by suffixing the path of the request with `@<version>`, eg. `users.get@v1..v3`, which is resolved as follows:
+///
+/// - `"latest"` - the highest registered version.
+/// - `"2"` or `"v2"` - exactly version 2.
+/// - `"1..3"` or `"v1..v3"` - the highest registered version between 1 and 3 (inclusive).
+///
+/// Over HTTP the version can also be sent in the `version` query parameter, eg. `/rspc/users.get?version=v2`.
+///
+/// If a request doesn't specify a version the unversioned procedure is used or if there isn't one the latest version.
+/// A request for a version which can't be parsed fails with a `400 Bad Request` error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum VersionReq {
+    Latest,
+    Exact(u32),
+    Range { min: u32, max: u32 },
+}
+
+impl VersionReq {
+    pub fn matches(&self, version: u32) -> bool {
+        match self {
+            Self::Latest => true,
+            Self::Exact(v) => *v == version,
+            Self::Range { min, max } => (*min..=*max).contains(&version),
+        }
+    }
+}
+
+/// Parse the version of a key defined as `name@v<number>`. Returns `None` if the key is not versioned.
+///
+/// Only the canonical form is accepted so two keys can't refer to the same version, eg. `get@1` or `get@v01` are not versioned keys.
+pub(crate) fn parse_versioned_key(key: &str) -> Option<(&str, u32)> {
+    let (name, version) = key.rsplit_once('@')?;
+    let version = version.strip_prefix('v')?;
+    let canonical = version.chars().all(|c| c.is_ascii_digit())
+        && (version == "0" || !version.starts_with('0'));
+
+    Some((name, version.parse().ok().filter(|_| canonical)?))
+}
+
+fn parse_version(s: &str) -> Option<u32> {
+    let s = s.strip_prefix('v').unwrap_or(s);
+    match !s.is_empty() && s.chars().all(|c| c.is_ascii_digit()) {
+        true => s.parse().ok(),
+        false => None,
+    }
+}
+
+impl FromStr for VersionReq {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "latest" {
+            return Ok(Self::Latest);
+        }
+
+        let parsed = match s.split_once("..") {
+            Some((min, max)) => parse_version(min)
+                .zip(parse_version(max))
+                .filter(|(min, max)| min <= max)
+                .map(|(min, max)| Self::Range { min, max }),
+            None => parse_version(s).map(Self::Exact),
+        };
+
+        parsed.ok_or_else(|| {
+            format!("invalid version '{s}'. Expected 'latest', a version like 'v2' or a range like 'v1..v3'")
+        })
+    }
+}
+
+impl TryFrom<String> for VersionReq {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<VersionReq> for String {
+    fn from(v: VersionReq) -> Self {
+        v.to_string()
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Latest => write!(f, "latest"),
+            Self::Exact(v) => write!(f, "v{v}"),
+            Self::Range { min, max } => write!(f, "v{min}..v{max}"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#![cfg(any(feature = "async-std", feature = "smol"))]
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use rspc::internal::exec::AsyncRuntime;
+
+async fn check_runtime<R: AsyncRuntime>() {
+    let start = Instant::now();
+    R::sleep(Duration::from_millis(10)).await;
+    assert!(start.elapsed() >= Duration::from_millis(10));
+
+    // A cancelled task never completes
+    let completed = Arc::new(AtomicBool::new(false));
+    let task = R::spawn({
+        let completed = completed.clone();
+        async move {
+            R::sleep(Duration::from_millis(10)).await;
+            completed.store(true, Ordering::SeqCst);
+        }
+    });
+    R::cancel_task(task);
+    R::sleep(Duration::from_millis(50)).await;
+    assert!(!completed.load(Ordering::SeqCst));
+
+    // An uncancelled task does
+    let completed = Arc::new(AtomicBool::new(false));
+    let _task = R::spawn({
+        let completed = completed.clone();
+        async move { completed.store(true, Ordering::SeqCst) }
+    });
+    R::sleep(Duration::from_millis(50)).await;
+    assert!(completed.load(Ordering::SeqCst));
+}
+
+#[cfg(feature = "async-std")]
+#[test]
+fn test_async_std_runtime() {
+    async_std::task::block_on(check_runtime::<rspc::internal::exec::AsyncStdRuntime>());
+}
+
+#[cfg(feature = "smol")]
+#[test]
+fn test_smol_runtime() {
+    smol::block_on(check_runtime::<rspc::internal::exec::SmolRuntime>());
+}
@@ -4,10 +4,10 @@ use async_stream::stream;
 use rspc::{
     internal::{
         exec::{Executor, Request, ResponseInner},
-        middleware::{ConstrainedMiddleware, Middleware, SealedMiddleware},
+        middleware::{ConstrainedMiddleware, Middleware, ProcedureKind, SealedMiddleware},
     },
     unstable::{MwArgMapper, MwArgMapperMiddleware},
-    BuildResult, ExportConfig, Rspc,
+    BuildError, BuildResult, ErrorCode, ExportConfig, Rspc,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
@@ -71,6 +71,115 @@ async fn test_router_merging() {
     .await;
 }
 
+#[tokio::test]
+async fn test_procedure_meta() {
+    let r = R
+        .router()
+        .procedure(
+            "admin",
+            R.with(|mw, ctx| async move {
+                match mw.req.meta().and_then(|m| m.get("role")) {
+                    Some(role) if role == "admin" => Ok(mw.next(ctx)),
+                    _ => Err(rspc::Error::new(
+                        ErrorCode::Forbidden,
+                        "missing role".into(),
+                    )),
+                }
+            })
+            .query(|_, _: ()| Ok(()))
+            .description("An admin only procedure")
+            .tag("admin")
+            .meta("role", "admin"),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    let meta = r.procedure_meta(ProcedureKind::Query, "admin").unwrap();
+    assert_eq!(meta.description(), Some("An admin only procedure"));
+    assert_eq!(meta.tags().collect::<Vec<_>>(), vec!["admin"]);
+    assert_eq!(meta.deprecated(), None);
+    assert!(r.procedure_meta(ProcedureKind::Mutation, "admin").is_none());
+
+    // The middleware can only run the resolver if it can see the metadata
+    assert_resp(
+        &Executor::new(r),
+        Request::Query {
+            id: 0,
+            path: "admin".into(),
+            input: None,
+        },
+        ResponseInner::Value(Value::Null),
+    )
+    .await;
+}
+
+#[test]
+fn test_introspection() {
+    let r = R
+        .router()
+        .procedure("version", R.query(|_, _: ()| "1.0.0").tag("meta"))
+        .procedure("echo", R.mutation(|_, input: String| input))
+        .procedure(
+            "pings",
+            R.subscription(|_, _: ()| stream! { yield "ping"; }),
+        )
+        .build()
+        .unwrap();
+
+    let procedures = r
+        .procedures()
+        .map(|p| (p.kind, p.key, p.has_input()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        procedures,
+        vec![
+            (ProcedureKind::Query, "version", false),
+            (ProcedureKind::Mutation, "echo", true),
+            (ProcedureKind::Subscription, "pings", false),
+        ]
+    );
+
+    let version = r.procedure(ProcedureKind::Query, "version").unwrap();
+    assert_eq!(version.meta.tags().collect::<Vec<_>>(), vec!["meta"]);
+    assert_eq!(r.procedures_of(ProcedureKind::Mutation).count(), 1);
+    assert!(r.procedure(ProcedureKind::Mutation, "version").is_none());
+    assert!(r.type_defs().is_empty());
+}
+
+fn expose(err: &BuildError) -> (String, String) {
+    (err.key().to_string(), err.cause().to_string())
+}
+
+#[test]
+fn test_reserved_names() {
+    let result = R
+        .router()
+        .procedure("ws", R.query(|_, _: ()| Ok(())))
+        .procedure("rspc", R.query(|_, _: ()| Ok(())))
+        .merge("rpc", R.router().procedure("a", R.query(|_, _: ()| Ok(()))))
+        .build();
+
+    let errors = match result {
+        BuildResult::Err(e) => e,
+        BuildResult::Ok(_) => panic!("Expected error"),
+    };
+    let errors = errors.iter().map(expose).collect::<Vec<_>>();
+    assert_eq!(
+        errors
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        ["ws", "rspc", "rpc"]
+    );
+    for (name, cause) in &errors {
+        assert_eq!(
+            cause,
+            &format!("a procedure or router name is using the name '{name}' which is reserved for internal use.")
+        );
+    }
+}
+
 #[test]
 fn test_invalid_prefix() {
     const VERY_LONG_NAME: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
@@ -93,10 +202,10 @@ fn test_invalid_prefix() {
     };
     assert_eq!(errors.len(), 5);
 
-    assert_eq!(errors[0].expose(), ("@@@".into(), "a procedure or router name contains the character '@' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
-    assert_eq!(errors[1].expose(), ("demo.2".into(), "a procedure or router name contains the character '.' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
+    assert_eq!(expose(&errors[0]), ("@@@".into(), "a procedure or router name contains the character '@' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
+    assert_eq!(expose(&errors[1]), ("demo.2".into(), "a procedure or router name contains the character '.' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
     assert_eq!(
-        errors[2].expose(),
+        expose(&errors[2]),
         (
             "".into(),
             "a procedure or router name must be more than 1 character and less than 255 characters"
@@ -104,14 +213,91 @@ fn test_invalid_prefix() {
         )
     );
     assert_eq!(
-        errors[3].expose(),
+        expose(&errors[3]),
         (
             VERY_LONG_NAME.into(),
             "a procedure or router name must be more than 1 character and less than 255 characters"
                 .into()
         )
     );
-    assert_eq!(errors[4].expose(), ("invalid.prefix".into(), "a procedure or router name contains the character '.' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
+    assert_eq!(expose(&errors[4]), ("invalid.prefix".into(), "a procedure or router name contains the character '.' which is not allowed. Names must be alphanumeric or have '_' or '-'".into()));
+
+    // Errors can be displayed in all build profiles
+    assert_eq!(errors[0].location().file(), "tests/basic.rs");
+    assert!(errors[0].to_string().starts_with("'@@@' at 'tests/basic.rs:"));
+    assert!(errors.to_string().starts_with("error building rspc router\n  - '@@@' at"));
+}
+
+mod v1 {
+    use serde::Serialize;
+    use specta::Type;
+
+    #[derive(Serialize, Type)]
+    pub struct User {
+        pub name: String,
+    }
+}
+
+mod v2 {
+    use serde::Serialize;
+    use specta::Type;
+
+    #[derive(Serialize, Type)]
+    pub struct User {
+        pub id: u32,
+    }
+}
+
+#[test]
+fn test_build_errors() {
+    let users = || R.router().procedure("me", R.query(|_, _: ()| Ok(())));
+
+    let result = R
+        .router()
+        .procedure("a", R.query(|_, _: ()| Ok(())))
+        .procedure("a", R.mutation(|_, _: ()| Ok(())))
+        .procedure("a", R.query(|_, _: ()| Ok(())))
+        .merge("users", users())
+        .merge("users", users())
+        .procedure("userV1", R.query(|_, _: ()| v1::User { name: "".into() }))
+        .procedure("userV2", R.query(|_, _: ()| v2::User { id: 0 }))
+        .build();
+
+    let errors = match result {
+        BuildResult::Err(e) => e,
+        BuildResult::Ok(_) => panic!("Expected error"),
+    };
+    let errors = errors.iter().map(expose).collect::<Vec<_>>();
+    assert_eq!(errors.len(), 3);
+
+    // Both locations are reported. The error is at the second definition and the message points to the first.
+    let (name, cause) = &errors[0];
+    assert_eq!(name, "a");
+    assert!(cause.starts_with("a query with the same key is already defined at 'tests/basic.rs:"));
+
+    assert_eq!(errors[1].0, "users.me");
+    assert!(errors[1].1.starts_with("a query with the same key"));
+
+    assert_eq!(errors[2].0, "userV2");
+    assert!(errors[2].1.starts_with(
+        "the type 'User' has the same name as a different type which was first used by the procedure defined at 'tests/basic.rs:"
+    ));
+
+    let result = R
+        .router()
+        .procedure("a", R.query(|_, _: ()| Ok(())))
+        .merge("empty", R.router())
+        .build();
+
+    let errors = match result {
+        BuildResult::Err(e) => e,
+        BuildResult::Ok(_) => panic!("Expected error"),
+    };
+    assert_eq!(errors.len(), 1);
+    assert_eq!(expose(&errors[0]).0, "empty");
+    assert!(expose(&errors[0]).1.starts_with(
+        "the router merged under this prefix has no procedures. It was created at 'tests/basic.rs:"
+    ));
 }
 
 #[test]
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use async_stream::stream;
+use futures::StreamExt;
+use rspc::{Client, ErrorCode, Rspc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const R: Rspc<u32> = Rspc::new();
+
+#[derive(Serialize, Deserialize, Type, Debug, PartialEq)]
+struct User {
+    id: u32,
+    name: String,
+}
+
+fn client() -> Client<u32> {
+    R.router()
+        .procedure(
+            "me",
+            R.query(|id, _: ()| User {
+                id,
+                name: "Oscar".into(),
+            }),
+        )
+        .procedure(
+            "rename",
+            R.with(|mw, id| async move { mw.next(id * 10) })
+                .mutation(|id, name: String| User { id, name }),
+        )
+        .procedure(
+            "admin",
+            R.query(|_, _: ()| -> Result<(), rspc::Error> {
+                Err(rspc::Error::new(
+                    ErrorCode::Forbidden,
+                    "not an admin".into(),
+                ))
+            }),
+        )
+        .procedure(
+            "count",
+            R.subscription(|id, to: u32| {
+                stream! {
+                    for i in id..to {
+                        yield i;
+                    }
+                }
+            }),
+        )
+        .build()
+        .unwrap()
+        .arced()
+        .into()
+}
+
+#[tokio::test]
+async fn test_client() {
+    let client = client();
+
+    let me: User = client.query(1, "me", ()).await.unwrap();
+    assert_eq!(
+        me,
+        User {
+            id: 1,
+            name: "Oscar".into()
+        }
+    );
+
+    // Calls go through the middleware
+    let user: User = client.mutation(1, "rename", "Monty").await.unwrap();
+    assert_eq!(
+        user,
+        User {
+            id: 10,
+            name: "Monty".into()
+        }
+    );
+
+    let count = client
+        .subscription::<_, u32>(2, "count", 5)
+        .map(Result::unwrap)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(count, vec![2, 3, 4]);
+}
+
+#[tokio::test]
+async fn test_client_errors() {
+    let client = client();
+
+    let err = client.query::<_, ()>(1, "admin", ()).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::Forbidden);
+    assert_eq!(err.message(), "not an admin");
+
+    // Procedures are looked up by kind
+    let err = client
+        .query::<_, User>(1, "rename", "Monty")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    // The input must match the procedure
+    let err = client
+        .mutation::<_, User>(1, "rename", 42)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BadRequest);
+
+    // And so does the result
+    let err = client.query::<_, String>(1, "me", ()).await.unwrap_err();
+    assert_eq!(err.code(), ErrorCode::InternalServerError);
+    assert_eq!(err.message(), "error deserializing procedure result");
+
+    // JSON objects can only have string keys so the input can't be serialized
+    let input = HashMap::from([((1, 2), 3)]);
+    let err = client
+        .mutation::<_, User>(1, "rename", input)
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BadRequest);
+    assert_eq!(err.message(), "error serializing procedure arguments");
+}
+
+#[tokio::test]
+async fn test_client_versions() {
+    let client: Client<u32> = R
+        .router()
+        .procedure("get@v1", R.query(|_, _: ()| "v1"))
+        .procedure("get@v2", R.query(|_, _: ()| "v2"))
+        .build()
+        .unwrap()
+        .arced()
+        .into();
+
+    // An unversioned key resolves to the latest version
+    let v: String = client.query(1, "get", ()).await.unwrap();
+    assert_eq!(v, "v2");
+
+    let v: String = client.query(1, "get@v1", ()).await.unwrap();
+    assert_eq!(v, "v1");
+
+    let err = client
+        .query::<_, String>(1, "get@v3", ())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::NotFound);
+
+    let err = client
+        .query::<_, String>(1, "get@beta", ())
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), ErrorCode::BadRequest);
+}
@@ -1,105 +1,130 @@
-use rspc::Rspc;
+use std::time::Duration;
 
-mod utils;
-// use utils::*;
+use async_stream::stream;
+use futures::{stream::pending, StreamExt};
+use rspc::{
+    internal::exec::Executor,
+    testing::{self, MockConnection, BATCH_TIMEOUT},
+    ErrorCode, Rspc,
+};
 
 const R: Rspc<()> = Rspc::new();
 
-// TODO: Test duplicate subscription ID
-// TODO: Assert that the subscriptions are being shut down correctly and when expected
-// TODO: Test stopping subscriptions
-// TODO: Assert the state of the subscription map
-// TODO: Assert error when the transport doesn't support subscriptions
+fn connection() -> MockConnection<()> {
+    let r = R
+        .router()
+        .procedure("a", R.query(|_, _: ()| "a"))
+        .procedure("b", R.mutation(|_, _: ()| "b"))
+        .procedure(
+            "c",
+            R.subscription(|_, _: ()| {
+                stream! {
+                    yield 1;
+                    yield 2;
+                    yield 3;
+                }
+            }),
+        )
+        .procedure(
+            "d",
+            R.subscription(|_, input: String| futures::stream::iter([input]).chain(pending())),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    MockConnection::new(Executor::new(r), ())
+}
 
 #[tokio::test]
 async fn test_exec_subscription() {
-    // let r = R
-    //     .router()
-    //     .procedure("a", R.query(|_, _: ()| ""))
-    //     .procedure("b", R.mutation(|_, _: ()| ""))
-    //     .procedure(
-    //         "c",
-    //         R.subscription(|_, _: ()| {
-    //             stream! {
-    //                 yield 42;
-    //             }
-    //         }),
-    //     )
-    //     .procedure(
-    //         "d",
-    //         R.subscription(|_, _: ()| {
-    //             atomic_procedure!("d");
-    //             async move {
-    //                 stream! {
-    //                     yield 43;
-    //                 }
-    //             }
-    //         }),
-    //     )
-    //     .procedure(
-    //         "e",
-    //         R.subscription(|_, input: String| {
-    //             atomic_procedure!("e");
-    //             async move {
-    //                 stream! { yield input; }
-    //             }
-    //         }),
-    //     )
-    //     .procedure(
-    //         "f",
-    //         R.subscription(|_, _: ()| {
-    //             atomic_procedure!("f");
-    //             async move {
-    //                 stream! {
-    //                     yield 1;
-    //                     yield 2;
-    //                     yield 3;
-    //                 }
-    //             }
-    //         }),
-    //     )
-    //     .build()
-    //     .unwrap()
-    //     .arced();
-
-    // let e = Executor::<_, TokioRuntime>::new(r);
-
-    // Ensure request for subscription doesn't resolve to a query
-    // assert_resp(
-    //     &e,
-    //     Request::Subscription {
-    //         id: "1".into(),
-    //         path: "a".into(),
-    //         input: None,
-    //     },
-    //     ValueOrError::Error(ExecError::OperationNotFound.into()),
-    // )
-    // .await;
-
-    // Ensure request for subscription doesn't resolve to a mutation
-    // assert_resp(
-    //     &e,
-    //     Request::Subscription {
-    //         id: "1".into(),
-    //         path: "b".into(),
-    //         input: None,
-    //     },
-    //     ValueOrError::Error(ExecError::OperationNotFound.into()),
-    // )
-    // .await;
-
-    // Ensure duplicate keys throw error
-    // Ensure request for subscription doesn't resolve to a mutation
-    //  assert_resp(
-    //     &e,
-    //     Request::Subscription {
-    //         id: "1".into(),
-    //         path: "b".into(),
-    //         input: None,
-    //     },
-    //     ValueOrError::Error(ExecError::ErrSubscriptionDuplicateId.into()),
-    // )
-    // .await;
-
-    // TODO: Do more tests
+    let mut conn = connection();
+
+    conn.send(testing::subscription(1, "c", ()));
+    testing::assert_responses(
+        &conn.recv_responses(4).await,
+        &[
+            testing::value(1, 1),
+            testing::value(1, 2),
+            testing::value(1, 3),
+            testing::complete(1),
+        ],
+    );
+
+    // Queries and mutations can be sent over the connection
+    conn.send_batch([testing::query(2, "a", ()), testing::mutation(3, "b", ())]);
+    testing::assert_responses(
+        &conn.recv_responses(2).await,
+        &[testing::value(2, "a"), testing::value(3, "b")],
+    );
+
+    conn.close();
+    assert_eq!(conn.recv().await, None);
+}
+
+#[tokio::test]
+async fn test_exec_subscription_errors() {
+    let mut conn = connection();
+
+    // Ensure request for subscription doesn't resolve to a query or mutation
+    conn.send_batch([
+        testing::subscription(1, "a", ()),
+        testing::subscription(2, "b", ()),
+    ]);
+    let not_found = "the requested operation is not supported by this server";
+    testing::assert_responses(
+        &conn.recv_responses(2).await,
+        &[
+            testing::error(1, ErrorCode::NotFound, not_found),
+            testing::error(2, ErrorCode::NotFound, not_found),
+        ],
+    );
+
+    // Ensure duplicate ids throw an error
+    conn.send(testing::subscription(3, "d", "hello"));
+    testing::assert_responses(&conn.recv_responses(1).await, &[testing::value(3, "hello")]);
+    conn.send(testing::subscription(3, "d", "world"));
+    testing::assert_responses(
+        &conn.recv_responses(1).await,
+        &[testing::error(
+            3,
+            ErrorCode::BadRequest,
+            "error creating subscription with duplicate id",
+        )],
+    );
+}
+
+#[tokio::test]
+async fn test_stop_subscription() {
+    let mut conn = connection();
+
+    conn.send(testing::subscription(1, "d", "hello"));
+    testing::assert_responses(&conn.recv_responses(1).await, &[testing::value(1, "hello")]);
+
+    // Once stopped the id can be reused
+    conn.send(testing::subscription_stop(1));
+    conn.send(testing::subscription(1, "d", "world"));
+    testing::assert_responses(&conn.recv_responses(1).await, &[testing::value(1, "world")]);
+
+    conn.close();
+    assert_eq!(conn.recv().await, None);
+}
+
+#[tokio::test]
+async fn test_batching() {
+    let mut conn = connection();
+
+    conn.send(testing::query(1, "a", ()));
+    conn.send(testing::query(2, "a", ()));
+
+    // Nothing is sent until the batch timeout has passed
+    assert_eq!(conn.try_recv(), None);
+    conn.advance(BATCH_TIMEOUT - Duration::from_millis(1));
+    assert_eq!(conn.try_recv(), None);
+    conn.advance(Duration::from_millis(1));
+
+    // Both responses are sent in a single message
+    let msg = conn.try_recv().unwrap();
+    testing::assert_responses(&msg, &[testing::value(1, "a"), testing::value(2, "a")]);
+    assert_eq!(conn.elapsed(), BATCH_TIMEOUT);
 }
@@ -0,0 +1,198 @@
+use rspc::{
+    export::{BigIntExport, Python, Rust, Swift, Typescript},
+    testing, ExportConfig, ExportError, Rspc,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Deserialize, Type)]
+struct CreateUser {
+    name: String,
+    age: u8,
+}
+
+#[derive(Serialize, Type)]
+enum Status {
+    Active,
+    Banned,
+}
+
+fn router() -> rspc::BuiltRouter<()> {
+    R.router()
+        .procedure("status", R.query(|_, _: ()| Status::Active))
+        .procedure(
+            "createUser",
+            R.mutation(|_, user: CreateUser| user.name)
+                .description("Create a new user"),
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_export_python() {
+    let bindings = router().export_to_string(&Python::new()).unwrap();
+
+    assert!(bindings.contains(r#"CreateUser = TypedDict("CreateUser", {"name": str, "age": int})"#));
+    assert!(bindings.contains(r#"Status: TypeAlias = Union[Literal["Active"], Literal["Banned"]]"#));
+    assert!(bindings.contains(r#"def query_status(self) -> "Status":"#));
+    assert!(bindings.contains(r#"def mutation_create_user(self, input: "CreateUser") -> str:"#));
+    assert!(bindings.contains(r#""""Create a new user""""#));
+    assert!(bindings.contains(r#"return self._call("POST", "createUser", input)"#));
+}
+
+#[test]
+fn test_export_swift() {
+    let bindings = router().export_to_string(&Swift::new()).unwrap();
+
+    assert!(bindings.contains("public struct CreateUser: Codable {\n    public var name: String\n    public var age: UInt8\n}"));
+    assert!(bindings.contains("public enum Status: String, Codable {\n    case active = \"Active\"\n    case banned = \"Banned\"\n}"));
+    assert!(bindings.contains("public func queryStatus() async throws -> Status {"));
+    assert!(bindings.contains("    /// Create a new user\n    public func mutationCreateUser(_ input: CreateUser) async throws -> String {"));
+}
+
+#[test]
+fn test_export_rust() {
+    let bindings = router().export_to_string(&Rust::new()).unwrap();
+
+    assert!(bindings.contains("#[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]\npub struct CreateUser {\n    pub name: String,\n    pub age: u8,\n}"));
+    assert!(bindings.contains("pub enum Status {\n    Active,\n    Banned,\n}"));
+    assert!(bindings.contains("pub mod queries {\n    use super::*;\n\n    pub async fn status(client: &::rspc_client::Client) -> Result<Status, ::rspc_client::Error> {\n        client.query(\"status\", &()).await\n    }"));
+    assert!(bindings.contains("    /// Create a new user\n    pub async fn create_user(client: &::rspc_client::Client, input: &CreateUser) -> Result<String, ::rspc_client::Error> {\n        client.mutation(\"createUser\", input).await\n    }"));
+    assert!(!bindings.contains("pub mod subscriptions"));
+}
+
+#[derive(Serialize, Type)]
+struct File {
+    #[serde(with = "rspc::export::bigint")]
+    size: u64,
+}
+
+#[test]
+fn test_export_ts_split() {
+    let dir = std::env::temp_dir().join("rspc-test-export-ts-split");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    R.router()
+        .procedure("file", R.query(|_, _: ()| File { size: u64::MAX }))
+        .merge(
+            "users",
+            R.router()
+                .procedure("create", R.mutation(|_, user: CreateUser| user.name)),
+        )
+        .build()
+        .unwrap()
+        .export_ts(
+            ExportConfig::new(dir.join("bindings.ts"))
+                .bigint(BigIntExport::String)
+                .split_types("types")
+                .path_constants(true),
+        )
+        .unwrap();
+
+    let bindings = std::fs::read_to_string(dir.join("bindings.ts")).unwrap();
+    assert!(bindings.contains("import type { CreateUser, File } from \"./types\";"));
+    assert!(bindings.contains("export * from \"./types\";"));
+    assert!(bindings
+        .contains("export const mutations = {\n    usersCreate: \"users.create\",\n} as const;"));
+
+    let file = std::fs::read_to_string(dir.join("types/File.ts")).unwrap();
+    assert!(file.contains("export type File = { size: string }"));
+
+    let index = std::fs::read_to_string(dir.join("types/index.ts")).unwrap();
+    assert!(index.contains("export * from \"./CreateUser\";"));
+    assert!(index.contains("export * from \"./File\";"));
+
+    assert_eq!(
+        serde_json::to_value(File { size: u64::MAX }).unwrap(),
+        serde_json::json!({ "size": "18446744073709551615" })
+    );
+}
+
+#[derive(Serialize, Type)]
+struct UnannotatedFile {
+    size: u64,
+}
+
+#[test]
+fn test_export_bigint_string() {
+    let bindings = R
+        .router()
+        .procedure("file", R.query(|_, _: ()| File { size: 1 }))
+        .build()
+        .unwrap()
+        .export_to_string(&Typescript::new().bigint(BigIntExport::String))
+        .unwrap();
+    assert!(bindings.contains("export type File = { size: string }"));
+    assert_eq!(
+        serde_json::to_value(File { size: 1 }).unwrap(),
+        serde_json::json!({ "size": "1" })
+    );
+
+    // Specta can't tell if a field is annotated so this would also be exported as a `string` but is serialized as a number
+    assert_eq!(
+        serde_json::to_value(UnannotatedFile { size: 1 }).unwrap(),
+        serde_json::json!({ "size": 1 })
+    );
+}
+
+#[test]
+fn test_export_bigint_bigint() {
+    let bindings = R
+        .router()
+        .procedure("file", R.query(|_, _: ()| File { size: 1 }))
+        .build()
+        .unwrap()
+        .export_to_string(&Typescript::new().bigint(BigIntExport::BigInt))
+        .unwrap();
+    assert!(bindings.contains("export type File = { size: BigInt }"));
+
+    // The value is sent as a string which the frontend converts with `BigInt(value)`
+    assert_eq!(
+        serde_json::to_value(File { size: u64::MAX }).unwrap(),
+        serde_json::json!({ "size": "18446744073709551615" })
+    );
+    let file: FileInput =
+        serde_json::from_value(serde_json::json!({ "size": "18446744073709551615" })).unwrap();
+    assert_eq!(file.size, u64::MAX);
+}
+
+#[derive(Deserialize)]
+struct FileInput {
+    #[serde(with = "rspc::export::bigint")]
+    size: u64,
+}
+
+#[test]
+fn test_export_path_constants_collision() {
+    let router = R
+        .router()
+        .merge("a", R.router().procedure("b", R.query(|_, _: ()| "")))
+        .procedure("aB", R.query(|_, _: ()| ""))
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        router.export_to_string(&Typescript::new().path_constants(true)),
+        Err(ExportError::DuplicatePathConstant(name, _, _)) if name == "aB"
+    ));
+    assert!(router.export_to_string(&Typescript::new()).is_ok());
+}
+
+#[test]
+fn test_export_snapshot() {
+    let path = std::env::temp_dir().join("rspc-test-export-snapshot/bindings.py");
+    let _ = std::fs::remove_file(&path);
+
+    // The snapshot is created by the first run
+    testing::assert_export_snapshot(&router(), &Python::new(), &path);
+    testing::assert_export_snapshot(&router(), &Python::new(), &path);
+
+    std::fs::write(&path, "outdated").unwrap();
+    let result = std::panic::catch_unwind(|| {
+        testing::assert_export_snapshot(&router(), &Python::new(), &path)
+    });
+    assert!(result.is_err());
+}
@@ -0,0 +1,158 @@
+use rspc::{
+    internal::exec::{Executor, Request, ResponseError, ResponseInner},
+    Rspc,
+};
+use serde::Deserialize;
+use serde_json::json;
+use specta::Type;
+
+mod utils;
+use utils::*;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Deserialize, Type)]
+struct CreateUser {
+    name: String,
+    age: u8,
+}
+
+#[derive(Deserialize, Type)]
+struct UpdateUser {
+    name: String,
+    bio: Option<String>,
+}
+
+#[test]
+fn test_json_schema() {
+    let r = R
+        .router()
+        .procedure("createUser", R.mutation(|_, user: CreateUser| user.name))
+        .build()
+        .unwrap();
+
+    let schema = r.json_schema();
+    assert_eq!(
+        schema["$schema"],
+        "https://json-schema.org/draft/2020-12/schema"
+    );
+    assert_eq!(
+        schema["mutations"]["createUser"],
+        json!({
+            "input": { "$ref": "#/$defs/CreateUser" },
+            "result": { "type": "string" },
+        })
+    );
+    assert_eq!(
+        schema["$defs"]["CreateUser"]["properties"],
+        json!({
+            "name": { "type": "string" },
+            "age": { "type": "integer", "minimum": 0 },
+        })
+    );
+    assert_eq!(
+        schema["$defs"]["CreateUser"]["required"],
+        json!(["name", "age"])
+    );
+}
+
+#[tokio::test]
+async fn test_validate_input_schemas() {
+    let r = R
+        .router()
+        .procedure(
+            "createUser",
+            R.mutation(|_, user: CreateUser| {
+                atomic_procedure!("createUser");
+                user.name
+            }),
+        )
+        .build()
+        .unwrap()
+        .validate_input_schemas()
+        .arced();
+
+    let e = Executor::new(r);
+
+    // Every invalid field is reported, not just the first one Serde runs into
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "createUser".into(),
+            input: Some(json!({ "age": -1 })),
+        },
+        ResponseInner::Error(ResponseError {
+            code: 400,
+            message: "error validating procedure arguments; age: must be greater than or equal to 0; name: is required".into(),
+            data: Some(json!({
+                "fields": {
+                    "age": ["must be greater than or equal to 0"],
+                    "name": ["is required"],
+                }
+            })),
+        }),
+    )
+    .await;
+
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "createUser".into(),
+            input: Some(json!({ "name": "Oscar", "age": 20 })),
+        },
+        ResponseInner::Value(json!("Oscar")),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_validate_optional_fields() {
+    let r = R
+        .router()
+        .procedure(
+            "updateUser",
+            R.mutation(|_, user: UpdateUser| user.bio.unwrap_or(user.name)),
+        )
+        .build()
+        .unwrap()
+        .validate_input_schemas();
+    assert_eq!(
+        r.json_schema()["$defs"]["UpdateUser"]["required"],
+        json!(["name"])
+    );
+
+    let e = Executor::new(r.arced());
+
+    // Serde deserializes a missing `Option` as `None` so the input is valid
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "updateUser".into(),
+            input: Some(json!({ "name": "Oscar" })),
+        },
+        ResponseInner::Value(json!("Oscar")),
+    )
+    .await;
+
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "updateUser".into(),
+            input: Some(json!({ "name": "Oscar", "bio": 42 })),
+        },
+        ResponseInner::Error(ResponseError {
+            code: 400,
+            message: "error validating procedure arguments; bio: does not match any of the expected variants".into(),
+            data: Some(json!({
+                "fields": {
+                    "bio": ["does not match any of the expected variants"],
+                }
+            })),
+        }),
+    )
+    .await;
+}
@@ -0,0 +1,124 @@
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+use futures::stream;
+use rspc::{
+    internal::exec::{AsyncRuntime, Executor, LocalRuntime, SingleThreaded},
+    testing::{self, MockConnection},
+    Local, Rspc,
+};
+use tokio::task::LocalSet;
+
+#[derive(Clone)]
+struct Ctx {
+    count: Local<Rc<Cell<u32>>, LocalRuntime>,
+}
+
+const R: Rspc<Ctx> = Rspc::new();
+
+fn executor() -> Executor<Ctx> {
+    let router = R
+        .router()
+        .procedure(
+            "increment",
+            R.mutation(|ctx, _: ()| {
+                LocalRuntime::local(async move {
+                    // Holding an `Rc` across an await makes the future `!Send`
+                    let count: Rc<Cell<u32>> = (*ctx.count).clone();
+                    tokio::task::yield_now().await;
+                    count.set(count.get() + 1);
+                    count.get()
+                })
+            }),
+        )
+        .procedure(
+            "count",
+            R.subscription(|ctx, _: ()| {
+                let count: Rc<Cell<u32>> = (*ctx.count).clone();
+                LocalRuntime::local(stream::iter([count.get(), count.get() * 2]))
+            }),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    Executor::new(router)
+}
+
+fn ctx() -> Ctx {
+    Ctx {
+        count: Local::new(Rc::new(Cell::new(0))),
+    }
+}
+
+#[tokio::test]
+async fn test_local_resolvers() {
+    let executor = executor();
+    let ctx = ctx();
+
+    let resp = testing::exec(
+        &executor,
+        ctx.clone(),
+        testing::mutation(1, "increment", ()),
+    )
+    .await;
+    assert_eq!(resp, Some(testing::value(1, 1)));
+    let resp = testing::exec(
+        &executor,
+        ctx.clone(),
+        testing::mutation(2, "increment", ()),
+    )
+    .await;
+    assert_eq!(resp, Some(testing::value(2, 2)));
+    assert_eq!(ctx.count.get(), 2);
+
+    let mut conn = MockConnection::new(executor, ctx);
+    conn.send(testing::subscription(3, "count", ()));
+    testing::assert_responses(
+        &conn.recv_responses(3).await,
+        &[
+            testing::value(3, 2),
+            testing::value(3, 4),
+            testing::complete(3),
+        ],
+    );
+}
+
+#[test]
+fn test_local_wrong_thread() {
+    let value = LocalRuntime::local(Rc::new(42));
+    assert!(value.is_valid());
+
+    let value = std::thread::spawn(move || {
+        assert!(!value.is_valid());
+        value
+    })
+    .join()
+    .unwrap();
+    assert_eq!(**value, 42);
+}
+
+#[tokio::test]
+async fn test_local_runtime() {
+    LocalSet::new()
+        .run_until(async {
+            let ctx = ctx();
+            let task = LocalRuntime::spawn({
+                let ctx = ctx.clone();
+                async move { ctx.count.set(1) }
+            });
+            task.await.unwrap();
+            assert_eq!(ctx.count.get(), 1);
+
+            let task = LocalRuntime::spawn({
+                let ctx = ctx.clone();
+                async move {
+                    LocalRuntime::sleep(Duration::from_secs(60)).await;
+                    ctx.count.set(2)
+                }
+            });
+            LocalRuntime::cancel_task(task);
+            tokio::task::yield_now().await;
+            assert_eq!(ctx.count.get(), 1);
+        })
+        .await;
+}
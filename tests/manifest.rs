@@ -0,0 +1,141 @@
+use rspc::{Manifest, ProcedureKind, Rspc};
+use serde::Deserialize;
+use serde_json::json;
+use specta::Type;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Deserialize, Type)]
+struct CreateUser {
+    name: String,
+    age: Option<u8>,
+}
+
+#[test]
+fn test_manifest() {
+    let r = R
+        .router()
+        .procedure(
+            "version",
+            R.query(|_, _: ()| env!("CARGO_PKG_VERSION"))
+                .description("Get the version of the server"),
+        )
+        .procedure("createUser", R.mutation(|_, user: CreateUser| user.name))
+        .build()
+        .unwrap();
+
+    let manifest = r.manifest();
+    assert_eq!(manifest.version, Manifest::VERSION);
+    assert_eq!(manifest.procedures.len(), 2);
+
+    let version = manifest.procedure(ProcedureKind::Query, "version").unwrap();
+    assert_eq!(version.result, json!({ "type": "string" }));
+    assert_eq!(
+        version.meta.description(),
+        Some("Get the version of the server")
+    );
+
+    let create_user = manifest
+        .procedure(ProcedureKind::Mutation, "createUser")
+        .unwrap();
+    assert_eq!(create_user.input, json!({ "$ref": "#/types/CreateUser" }));
+
+    let ty = &manifest.types["CreateUser"];
+    assert_eq!(ty.hash.len(), 16);
+    // Serde accepts a missing `Option` so only `name` is required
+    assert_eq!(ty.schema["required"], json!(["name"]));
+
+    // The manifest is deterministic and can be read back.
+    assert_eq!(manifest, r.manifest());
+    let json = serde_json::to_value(&manifest).unwrap();
+    assert_eq!(json["procedures"][0]["kind"], "query");
+    assert_eq!(serde_json::from_value::<Manifest>(json).unwrap(), manifest);
+}
+
+mod v1 {
+    use serde::{Deserialize, Serialize};
+    use specta::Type;
+
+    #[derive(Serialize, Deserialize, Type)]
+    pub struct User {
+        pub name: String,
+        pub email: String,
+    }
+}
+
+mod v2 {
+    use serde::{Deserialize, Serialize};
+    use specta::Type;
+
+    #[derive(Serialize, Deserialize, Type)]
+    pub struct User {
+        pub name: String,
+        #[specta(optional)]
+        pub bio: Option<String>,
+    }
+}
+
+#[test]
+fn test_manifest_diff() {
+    let old = R
+        .router()
+        .procedure("version", R.query(|_, _: ()| "1.0.0"))
+        .procedure(
+            "me",
+            R.query(|_, _: ()| v1::User {
+                name: "".into(),
+                email: "".into(),
+            }),
+        )
+        .procedure("createUser", R.mutation(|_, user: v1::User| user.name))
+        .procedure("ping", R.query(|_, _: ()| "pong"))
+        .build()
+        .unwrap();
+
+    let new = R
+        .router()
+        .procedure(
+            "me",
+            R.query(|_, _: ()| v2::User {
+                name: "".into(),
+                bio: None,
+            }),
+        )
+        .procedure("createUser", R.mutation(|_, user: v2::User| user.name))
+        .procedure("ping", R.mutation(|_, _: ()| "pong"))
+        .procedure("health", R.query(|_, _: ()| true))
+        .build()
+        .unwrap();
+
+    let diff = old.manifest().diff(&new.manifest());
+    assert!(diff.is_breaking());
+
+    let changes = diff
+        .changes
+        .iter()
+        .map(|c| (c.key.as_str(), c.path.as_str(), c.breaking))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            ("me", "result.email", true),
+            ("me", "result.bio", false),
+            ("ping", "", true),
+            ("version", "", true),
+            ("createUser", "input.email", false),
+            ("createUser", "input.bio", false),
+            ("health", "", false),
+        ]
+    );
+    assert_eq!(
+        diff.changes[0].to_string(),
+        "[breaking] query `me` result.email: required field was removed"
+    );
+
+    // Changes which are safe in one direction can break the other.
+    let diff = new.manifest().diff(&old.manifest());
+    assert!(diff
+        .breaking()
+        .any(|c| c.key == "createUser" && c.path == "input.email"));
+    assert!(!old.manifest().diff(&old.manifest()).is_breaking());
+}
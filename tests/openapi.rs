@@ -0,0 +1,68 @@
+use rspc::{OpenApiConfig, Rspc};
+use serde::Deserialize;
+use serde_json::json;
+use specta::Type;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Deserialize, Type)]
+struct CreateUser {
+    name: String,
+    age: Option<u8>,
+}
+
+#[test]
+fn test_openapi() {
+    let r = R
+        .router()
+        .procedure(
+            "version",
+            R.query(|_, _: ()| env!("CARGO_PKG_VERSION"))
+                .description("Get the version of the server")
+                .tag("meta"),
+        )
+        .procedure("createUser", R.mutation(|_, user: CreateUser| user.name))
+        .procedure(
+            "pings",
+            R.subscription(|_, _: ()| async_stream::stream! { yield "ping"; }),
+        )
+        .build()
+        .unwrap();
+
+    let doc = r.openapi(&OpenApiConfig::new("Test", "1.0.0").server("http://localhost/rspc"));
+
+    assert_eq!(doc["openapi"], "3.1.0");
+    assert_eq!(doc["info"], json!({ "title": "Test", "version": "1.0.0" }));
+    assert_eq!(doc["servers"], json!([{ "url": "http://localhost/rspc" }]));
+
+    let version = &doc["paths"]["/version"]["get"];
+    assert_eq!(version["parameters"], json!([]));
+    assert_eq!(version["description"], "Get the version of the server");
+    assert_eq!(version["tags"], json!(["meta"]));
+    assert_eq!(
+        version["responses"]["200"]["content"]["application/json"]["schema"]["oneOf"][0]
+            ["properties"]["value"],
+        json!({ "type": "string" })
+    );
+    for status in ["400", "403", "404", "413"] {
+        assert_eq!(
+            version["responses"][status]["content"]["application/json"]["schema"]["properties"]
+                ["type"],
+            json!({ "const": "error" })
+        );
+    }
+
+    let create_user = &doc["paths"]["/createUser"]["post"];
+    assert_eq!(
+        create_user["requestBody"]["content"]["application/json"]["schema"],
+        json!({ "$ref": "#/components/schemas/CreateUser" })
+    );
+    assert_eq!(
+        doc["components"]["schemas"]["CreateUser"]["properties"]["name"],
+        json!({ "type": "string" })
+    );
+
+    let pings = &doc["paths"]["/pings"]["x-rspc-subscription"];
+    assert_eq!(pings["endpoint"], "/ws");
+    assert_eq!(pings["result"], json!({ "type": "string" }));
+}
@@ -0,0 +1,178 @@
+//! Requests and subscriptions own the procedures they were created from so the router can be swapped or dropped while they are running.
+//!
+//! These tests don't use a runtime so they can be run under Miri: `cargo +nightly miri test --features testing --test ownership`
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+};
+
+use futures::{stream, task::noop_waker_ref, FutureExt, StreamExt};
+use rspc::{
+    internal::exec::{
+        Executor, ExecutorResult, NoOpSubscriptionManager, OwnedStream, SubscriptionManager,
+        SubscriptionSet,
+    },
+    testing, BuiltRouter, Rspc, SwappableRouter,
+};
+
+const R: Rspc<()> = Rspc::new();
+
+/// Returns `Poll::Pending` once so the request is in-flight when the router is dropped.
+#[derive(Default)]
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn router() -> Arc<BuiltRouter<()>> {
+    let inner = R
+        .router()
+        .procedure(
+            "query",
+            R.with(|mw, ctx| async move { mw.next(ctx) })
+                .query(|_, _: ()| async {
+                    YieldOnce::default().await;
+                    "done"
+                }),
+        )
+        .procedure(
+            "subscription",
+            R.with(|mw, ctx| async move { mw.next(ctx) })
+                .subscription(|_, _: ()| {
+                    stream::unfold(0, |i| async move {
+                        YieldOnce::default().await;
+                        (i < 3).then_some((i, i + 1))
+                    })
+                }),
+        );
+
+    // Merging with a different context wraps the procedures in another layer
+    R.router()
+        .merge_with("inner", inner, Ok)
+        .build()
+        .unwrap()
+        .arced()
+}
+
+fn executor() -> (Executor<()>, Weak<BuiltRouter<()>>) {
+    let router = router();
+    let weak = Arc::downgrade(&router);
+    (Executor::new(SwappableRouter::new(router)), weak)
+}
+
+#[derive(Default)]
+struct Manager {
+    set: SubscriptionSet,
+    queued: Vec<OwnedStream<()>>,
+}
+
+impl SubscriptionManager<()> for Manager {
+    type Set<'m> = &'m mut SubscriptionSet;
+
+    fn queue(&mut self, stream: OwnedStream<()>) {
+        self.queued.push(stream);
+    }
+
+    fn subscriptions(&mut self) -> Self::Set<'_> {
+        &mut self.set
+    }
+
+    fn abort_subscription(&mut self, id: u32) {
+        self.set.remove(&id);
+        self.queued.retain(|s| s.id != id);
+    }
+}
+
+#[test]
+fn test_drop_router_during_request() {
+    let (executor, weak) = executor();
+    let mut fut = match executor.execute(
+        (),
+        testing::query(1, "inner.query", ()),
+        &mut (None as Option<NoOpSubscriptionManager>),
+    ) {
+        ExecutorResult::FutureResponse(fut) => fut,
+        _ => panic!("expected a future"),
+    };
+
+    let mut cx = Context::from_waker(noop_waker_ref());
+    assert!(fut.poll_unpin(&mut cx).is_pending());
+
+    drop(executor);
+    assert!(weak.upgrade().is_none(), "router should have been dropped");
+
+    assert_eq!(
+        fut.poll_unpin(&mut cx),
+        Poll::Ready(testing::value(1, "done"))
+    );
+}
+
+#[test]
+fn test_swap_router_during_subscription() {
+    let original = router();
+    let weak = Arc::downgrade(&original);
+    let swappable = SwappableRouter::new(original);
+    let executor = Executor::new(swappable.clone());
+
+    let mut manager = Some(Manager::default());
+    assert!(matches!(
+        executor.execute(
+            (),
+            testing::subscription(1, "inner.subscription", ()),
+            &mut manager
+        ),
+        ExecutorResult::None
+    ));
+    let mut stream = manager.unwrap().queued.remove(0);
+
+    let mut cx = Context::from_waker(noop_waker_ref());
+    let mut values = Vec::new();
+    while values.is_empty() {
+        if let Poll::Ready(Some(value)) = stream.poll_next_unpin(&mut cx) {
+            values.push(value.unwrap());
+        }
+    }
+
+    // Nothing else references the original router once it's swapped out
+    drop(swappable.swap(router()));
+    assert!(weak.upgrade().is_none(), "router should have been dropped");
+
+    loop {
+        match stream.poll_next_unpin(&mut cx) {
+            Poll::Ready(Some(value)) => values.push(value.unwrap()),
+            Poll::Ready(None) => break,
+            Poll::Pending => {}
+        }
+    }
+    assert_eq!(values, [0, 1, 2]);
+}
+
+#[test]
+fn test_drop_stream_after_router() {
+    let (executor, weak) = executor();
+
+    let mut manager = Some(Manager::default());
+    executor.execute(
+        (),
+        testing::subscription(1, "inner.subscription", ()),
+        &mut manager,
+    );
+    drop(executor);
+    assert!(weak.upgrade().is_none(), "router should have been dropped");
+
+    // The stream was never polled and is dropped after the router
+    drop(manager);
+}
@@ -0,0 +1,142 @@
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rspc::{
+    internal::exec::{Executor, TokioRuntime},
+    replay,
+    testing::{self, MockConnection},
+    BuiltRouter, Record, RecordedMessage, Recorder, Rspc,
+};
+use serde_json::json;
+
+const R: Rspc<()> = Rspc::new();
+
+fn router(greeting: &'static str) -> Arc<BuiltRouter<()>> {
+    R.router()
+        .procedure(
+            "greet",
+            R.query(move |_, name: String| format!("{greeting} {name}")),
+        )
+        .procedure(
+            "count",
+            R.subscription(|_, _: ()| futures::stream::iter([1, 2])),
+        )
+        .build()
+        .unwrap()
+        .arced()
+}
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Buffer {
+    fn records(&self) -> Vec<Record> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+}
+
+async fn record(recorder: Recorder) {
+    let mut conn = MockConnection::new(
+        Executor::new(router("Hello")).with_recorder(recorder.clone()),
+        (),
+    );
+
+    conn.send(testing::query(1, "greet", "Oscar"));
+    conn.recv_responses(1).await;
+    conn.send(testing::subscription(2, "count", ()));
+    conn.recv_responses(3).await;
+    conn.close();
+    while conn.recv().await.is_some() {}
+
+    // Records are written on a background thread
+    recorder.flush();
+}
+
+#[tokio::test]
+async fn test_recorder() {
+    let buf = Buffer::default();
+    record(Recorder::new(buf.clone())).await;
+
+    let messages = buf
+        .records()
+        .into_iter()
+        .map(|record| {
+            assert_eq!(record.connection, 0);
+            record.message
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            RecordedMessage::Request(
+                json!({ "method": "query", "id": 1, "path": "greet", "input": "Oscar" })
+            ),
+            RecordedMessage::Response(json!({ "id": 1, "type": "value", "value": "Hello Oscar" })),
+            RecordedMessage::Request(
+                json!({ "method": "subscription", "id": 2, "path": "count", "input": null })
+            ),
+            RecordedMessage::Response(json!({ "id": 2, "type": "value", "value": 1 })),
+            RecordedMessage::Response(json!({ "id": 2, "type": "value", "value": 2 })),
+            RecordedMessage::Response(json!({ "id": 2, "type": "complete" })),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_recorder_redact() {
+    let buf = Buffer::default();
+    record(Recorder::new(buf.clone()).redact(|record| {
+        if let Some(input) = record.request_mut().and_then(|req| req.get_mut("input")) {
+            *input = "<redacted>".into();
+        }
+    }))
+    .await;
+
+    let records = buf.records();
+    assert_eq!(
+        records[0].message,
+        RecordedMessage::Request(
+            json!({ "method": "query", "id": 1, "path": "greet", "input": "<redacted>" })
+        )
+    );
+    // Responses are untouched
+    assert_eq!(
+        records[1].message,
+        RecordedMessage::Response(json!({ "id": 1, "type": "value", "value": "Hello Oscar" }))
+    );
+}
+
+#[tokio::test]
+async fn test_replay() {
+    let buf = Buffer::default();
+    record(Recorder::new(buf.clone())).await;
+
+    let timeout = Duration::from_secs(1);
+    let diffs = replay::<TokioRuntime, _>(router("Hello"), |_| (), buf.records(), timeout).await;
+    assert_eq!(diffs, []);
+
+    let diffs = replay::<TokioRuntime, _>(router("Hi"), |_| (), buf.records(), timeout).await;
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].id, 1);
+    assert_eq!(
+        diffs[0].actual,
+        [json!({ "id": 1, "type": "value", "value": "Hi Oscar" })]
+    );
+}
@@ -0,0 +1,140 @@
+use rspc::{
+    internal::exec::{
+        Executor, ExecutorResult, NoOpSubscriptionManager, Request, Response, ResponseError,
+        ResponseInner,
+    },
+    ErrorCode, Rspc,
+};
+use serde::Serialize;
+use serde_json::json;
+use specta::Type;
+
+mod utils;
+pub use utils::*;
+
+const R: Rspc<()> = Rspc::new();
+
+// The id of the authenticated user
+const AR: Rspc<u32> = Rspc::new();
+
+fn query(path: &'static str) -> Request {
+    Request::Query {
+        id: 0,
+        path: path.into(),
+        input: None,
+    }
+}
+
+#[tokio::test]
+async fn test_router_middleware() {
+    let users = AR
+        .router()
+        .procedure("me", AR.query(|user_id, _: ()| user_id))
+        .procedure(
+            "next",
+            AR.with(|mw, user_id| async move { mw.next(user_id + 1) })
+                .query(|user_id, _: ()| user_id),
+        )
+        .procedure("blocked", AR.query(|user_id, _: ()| user_id))
+        .with(|mw, _: ()| async move {
+            match mw.req.path.as_ref() {
+                "users.blocked" => Err(rspc::Error::new(ErrorCode::Forbidden, "blocked".into())),
+                _ => Ok(mw.next(42u32)),
+            }
+        });
+
+    let r = R
+        .router()
+        .procedure("version", R.query(|_, _: ()| "1.0.0"))
+        .merge("users", users)
+        .build()
+        .unwrap();
+    assert_eq!(r.procedures().count(), 4);
+
+    let e = Executor::new(r.arced());
+    assert_resp(&e, query("version"), ResponseInner::Value(json!("1.0.0"))).await;
+    assert_resp(&e, query("users.me"), ResponseInner::Value(json!(42))).await;
+
+    // The router middleware runs before the procedure's own middleware
+    assert_resp(&e, query("users.next"), ResponseInner::Value(json!(43))).await;
+
+    assert_resp(
+        &e,
+        query("users.blocked"),
+        ResponseInner::Error(ResponseError {
+            code: 403,
+            message: "blocked".into(),
+            data: None,
+        }),
+    )
+    .await;
+}
+
+#[derive(Serialize, Type)]
+struct User {
+    id: u32,
+}
+
+#[derive(Serialize, Type)]
+struct Version {
+    major: u32,
+}
+
+async fn exec_with<TCtx: Send + 'static>(
+    e: &Executor<TCtx>,
+    ctx: TCtx,
+    req: Request,
+) -> ResponseInner {
+    match e.execute(ctx, req, &mut (None as Option<NoOpSubscriptionManager>)) {
+        ExecutorResult::FutureResponse(fut) => fut.await.inner,
+        ExecutorResult::Response(Response { inner, .. }) => inner,
+        ExecutorResult::None => unreachable!(),
+    }
+}
+
+#[tokio::test]
+async fn test_merge_with() {
+    // The parent context is the id of the user if they are logged in
+    const R: Rspc<Option<u32>> = Rspc::new();
+
+    let users = AR
+        .router()
+        .procedure("me", AR.query(|id, _: ()| User { id }));
+
+    let r = R
+        .router()
+        .procedure("version", R.query(|_, _: ()| Version { major: 1 }))
+        .merge_with("users", users, |ctx: Option<u32>| {
+            ctx.ok_or_else(|| rspc::Error::new(ErrorCode::Unauthorized, "not logged in".into()))
+        })
+        .build()
+        .unwrap();
+
+    // The types of both routers are exported
+    let mut types = r
+        .type_defs()
+        .values()
+        .flatten()
+        .map(|ty| ty.name)
+        .collect::<Vec<_>>();
+    types.sort();
+    assert_eq!(types, vec!["User", "Version"]);
+
+    let e = Executor::new(r.arced());
+    assert_eq!(
+        exec_with(&e, Some(1), query("users.me")).await,
+        ResponseInner::Value(json!({ "id": 1 }))
+    );
+    assert_eq!(
+        exec_with(&e, None, query("users.me")).await,
+        ResponseInner::Error(ResponseError {
+            code: 401,
+            message: "error mapping the context of the procedure: not logged in".into(),
+            data: None,
+        })
+    );
+    assert_eq!(
+        exec_with(&e, None, query("version")).await,
+        ResponseInner::Value(json!({ "major": 1 }))
+    );
+}
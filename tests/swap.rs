@@ -0,0 +1,74 @@
+use rspc::{
+    internal::exec::{
+        Executor, ExecutorResult, NoOpSubscriptionManager, Request, Response, ResponseInner,
+    },
+    BuiltRouter, Rspc, SwappableRouter,
+};
+use serde_json::json;
+use std::sync::Arc;
+
+mod utils;
+pub use utils::*;
+
+const R: Rspc<()> = Rspc::new();
+
+fn router(version: &'static str) -> Arc<BuiltRouter<()>> {
+    R.router()
+        .procedure("version", R.query(move |_, _: ()| version))
+        .build()
+        .unwrap()
+        .arced()
+}
+
+fn version(id: u32) -> Request {
+    Request::Query {
+        id,
+        path: "version".into(),
+        input: None,
+    }
+}
+
+#[tokio::test]
+async fn test_swap_router() {
+    let swappable = SwappableRouter::new(router("1"));
+    let executor = Executor::new(swappable.clone());
+
+    assert_resp(&executor, version(0), ResponseInner::Value(json!("1"))).await;
+
+    // A request which started before the swap finishes using the old router
+    let fut = match executor.execute(
+        (),
+        version(1),
+        &mut (None as Option<NoOpSubscriptionManager>),
+    ) {
+        ExecutorResult::FutureResponse(fut) => fut,
+        _ => unreachable!(),
+    };
+
+    drop(swappable.swap(router("2")));
+    assert_eq!(
+        fut.await,
+        Response {
+            id: 1,
+            inner: ResponseInner::Value(json!("1"))
+        }
+    );
+
+    // Clones of the executor share the router
+    assert_resp(
+        &executor.clone(),
+        version(2),
+        ResponseInner::Value(json!("2")),
+    )
+    .await;
+
+    let old = executor.swap_router(R.router().build().unwrap().arced());
+    assert_eq!(old.procedures().count(), 1);
+    assert!(matches!(
+        utils::exec(&executor, version(3)).await,
+        Some(Response {
+            inner: ResponseInner::Error(_),
+            ..
+        })
+    ));
+}
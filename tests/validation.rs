@@ -0,0 +1,90 @@
+use rspc::{
+    internal::exec::{Executor, Request, ResponseError, ResponseInner},
+    Rspc, Validate, ValidationError,
+};
+use serde::Deserialize;
+use serde_json::json;
+use specta::Type;
+
+mod utils;
+use utils::*;
+
+const R: Rspc<()> = Rspc::new();
+
+#[derive(Deserialize, Type)]
+struct CreateUser {
+    name: String,
+    age: u8,
+}
+
+impl Validate for CreateUser {
+    fn validate(&self) -> Result<(), ValidationError> {
+        let mut err = ValidationError::new();
+        if self.name.is_empty() {
+            err.add("name", "must not be empty");
+        }
+        err.into_result()
+    }
+}
+
+#[tokio::test]
+async fn test_validation() {
+    let r = R
+        .router()
+        .procedure(
+            "createUser",
+            R.mutation(|_, user: CreateUser| {
+                atomic_procedure!("createUser");
+                user.name
+            })
+            .validate(),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    let e = Executor::new(r);
+
+    // Invalid input never reaches the resolver
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "createUser".into(),
+            input: Some(json!({ "name": "", "age": 20 })),
+        },
+        ResponseInner::Error(ResponseError {
+            code: 400,
+            message: "error validating procedure arguments; name: must not be empty".into(),
+            data: Some(json!({ "fields": { "name": ["must not be empty"] } })),
+        }),
+    )
+    .await;
+
+    // Deserialization errors use the same shape with the path of the field
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "createUser".into(),
+            input: Some(json!({ "name": "Oscar", "age": "old" })),
+        },
+        ResponseInner::Error(ResponseError {
+            code: 400,
+            message: "error validating procedure arguments; age: invalid type: string \"old\", expected u8".into(),
+            data: Some(json!({ "fields": { "age": ["invalid type: string \"old\", expected u8"] } })),
+        }),
+    )
+    .await;
+
+    assert_resp(
+        &e,
+        Request::Mutation {
+            id: 0,
+            path: "createUser".into(),
+            input: Some(json!({ "name": "Oscar", "age": 20 })),
+        },
+        ResponseInner::Value(json!("Oscar")),
+    )
+    .await;
+}
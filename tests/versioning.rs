@@ -0,0 +1,128 @@
+use rspc::{
+    export::Typescript,
+    internal::exec::{Executor, Request, Response, ResponseInner},
+    BuildResult, Rspc, VersionReq,
+};
+use serde_json::json;
+
+mod utils;
+pub use utils::*;
+
+const R: Rspc<()> = Rspc::new();
+
+fn get(path: &'static str) -> Request {
+    Request::Query {
+        id: 0,
+        path: path.into(),
+        input: None,
+    }
+}
+
+#[tokio::test]
+async fn test_procedure_versions() {
+    let r = R
+        .router()
+        .merge(
+            "users",
+            R.router()
+                .procedure("get@v1", R.query(|_, _: ()| "v1"))
+                .procedure("get@v2", R.query(|_, _: ()| "v2"))
+                .procedure("get@v10", R.query(|_, _: ()| "v10")),
+        )
+        .build()
+        .unwrap()
+        .arced();
+
+    let bindings = r.export_to_string(&Typescript::new()).unwrap();
+    assert!(bindings.contains(r#"key: "users.get@v2""#));
+
+    let e = Executor::new(r);
+    assert_resp(&e, get("users.get"), ResponseInner::Value(json!("v10"))).await;
+    assert_resp(
+        &e,
+        get("users.get@latest"),
+        ResponseInner::Value(json!("v10")),
+    )
+    .await;
+    assert_resp(&e, get("users.get@2"), ResponseInner::Value(json!("v2"))).await;
+    assert_resp(
+        &e,
+        get("users.get@v1..v9"),
+        ResponseInner::Value(json!("v2")),
+    )
+    .await;
+
+    // A version can be called directly using its full key
+    assert_resp(&e, get("users.get@v1"), ResponseInner::Value(json!("v1"))).await;
+
+    // The version is part of the path in the request envelope
+    let req: Request = serde_json::from_value(
+        json!({ "method": "query", "id": 0, "path": "users.get@v1..v3", "input": null }),
+    )
+    .unwrap();
+    assert_resp(&e, req, ResponseInner::Value(json!("v2"))).await;
+}
+
+#[tokio::test]
+async fn test_procedure_version_errors() {
+    let e = Executor::new(
+        R.router()
+            .procedure("get@v1", R.query(|_, _: ()| "v1"))
+            .build()
+            .unwrap()
+            .arced(),
+    );
+
+    // No version matches
+    assert!(matches!(
+        exec(&e, get("get@v3")).await,
+        Some(Response {
+            inner: ResponseInner::Error(err),
+            ..
+        }) if err.code == 404
+    ));
+
+    // The version can't be parsed
+    assert!(matches!(
+        exec(&e, get("get@beta")).await,
+        Some(Response {
+            inner: ResponseInner::Error(err),
+            ..
+        }) if err.code == 400
+    ));
+}
+
+#[test]
+fn test_version_req() {
+    assert_eq!("latest".parse(), Ok(VersionReq::Latest));
+    assert_eq!("v2".parse(), Ok(VersionReq::Exact(2)));
+    assert_eq!("2".parse(), Ok(VersionReq::Exact(2)));
+    assert_eq!("v1..v3".parse(), Ok(VersionReq::Range { min: 1, max: 3 }));
+    assert!("v3..v1".parse::<VersionReq>().is_err());
+    assert!("beta".parse::<VersionReq>().is_err());
+    assert!("".parse::<VersionReq>().is_err());
+    assert_eq!(VersionReq::Range { min: 1, max: 3 }.to_string(), "v1..v3");
+}
+
+#[test]
+fn test_versioned_keys() {
+    for key in ["get@beta", "get@1", "get@v01", "get@vv1", "get@v"] {
+        let result = R.router().procedure(key, R.query(|_, _: ()| "")).build();
+        assert!(matches!(result, BuildResult::Err(_)), "{key}");
+    }
+
+    // `get@1` and `get@v1` would both be version 1 so only the canonical form is allowed
+    let result = R
+        .router()
+        .procedure("get@v1", R.query(|_, _: ()| "v1"))
+        .procedure("get@1", R.query(|_, _: ()| "1"))
+        .build();
+    assert!(matches!(result, BuildResult::Err(_)));
+
+    let result = R
+        .router()
+        .procedure("get@v0", R.query(|_, _: ()| "v0"))
+        .procedure("get@v1", R.query(|_, _: ()| "v1"))
+        .build();
+    assert!(matches!(result, BuildResult::Ok(_)));
+}